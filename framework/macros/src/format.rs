@@ -0,0 +1,133 @@
+use crate::byte_string;
+use std::fmt::Write;
+
+const INTEGERS: [&str; 12] = [
+    "u8", "u16", "u32", "u64", "u128", "usize", "i8", "i16", "i32", "i64", "i128", "isize",
+];
+
+enum Segment<'t> {
+    Literal(Vec<u8>),
+    Placeholder(&'t str, Option<&'t str>),
+}
+
+fn split_template(template: &[u8]) -> Result<Vec<Segment<'_>>, String> {
+    let mut segments = Vec::new();
+    let mut literal = Vec::new();
+    let mut index = 0;
+    while index < template.len() {
+        match (template[index], template.get(index + 1)) {
+            (b'{', Some(b'{')) | (b'}', Some(b'}')) => {
+                literal.push(template[index]);
+                index += 2;
+            }
+            (b'{', _) => {
+                let length = template[index..]
+                    .iter()
+                    .position(|&c| c == b'}')
+                    .ok_or("unterminated placeholder in template")?;
+                let placeholder = std::str::from_utf8(&template[index + 1..index + length])
+                    .map_err(|_| "placeholder is not valid UTF-8")?;
+                let (kind, modifier) = match placeholder.split_once(':') {
+                    Some((kind, modifier)) => (kind.trim(), Some(modifier.trim())),
+                    None => (placeholder.trim(), None),
+                };
+                if !literal.is_empty() {
+                    segments.push(Segment::Literal(std::mem::take(&mut literal)));
+                }
+                segments.push(Segment::Placeholder(kind, modifier));
+                index += length + 1;
+            }
+            (b'}', _) => return Err("unmatched `}` in template, use `}}` to escape it".to_owned()),
+            (c, _) => {
+                literal.push(c);
+                index += 1;
+            }
+        }
+    }
+    if !literal.is_empty() {
+        segments.push(Segment::Literal(literal));
+    }
+    Ok(segments)
+}
+
+/// Returns the output type, and an expression that evaluates to the parser.
+fn placeholder_parser(kind: &str, modifier: Option<&str>) -> Result<(String, String), String> {
+    Ok(match (kind, modifier) {
+        (kind, None) if INTEGERS.contains(&kind) => (
+            kind.to_owned(),
+            format!("__framework::parsers::number::<{kind}>()"),
+        ),
+        (kind @ ("f32" | "f64"), None) => (
+            kind.to_owned(),
+            format!("__framework::parsers::number::<{kind}>()"),
+        ),
+        (kind, Some(radix @ ("hex" | "oct" | "bin"))) if INTEGERS.contains(&kind) => (
+            kind.to_owned(),
+            format!("__framework::parsers::{radix}::<{kind}>()"),
+        ),
+        (kind, Some("digit")) if INTEGERS.contains(&kind) => (
+            kind.to_owned(),
+            format!("__framework::parsers::digit().map(|digit| digit as {kind})"),
+        ),
+        ("word", None) => (
+            "&'s [u8]".to_owned(),
+            "__framework::parsers::take_while((), |_, c: u8| c.is_ascii_alphabetic())".to_owned(),
+        ),
+        ("byte", None) => ("u8".to_owned(), "__framework::parsers::any()".to_owned()),
+        (kind, Some(modifier)) => {
+            return Err(format!("unsupported placeholder `{{{kind}:{modifier}}}`"));
+        }
+        (kind, None) => return Err(format!("unsupported placeholder `{{{kind}}}`")),
+    })
+}
+
+pub fn expand(template: &[u8]) -> Result<String, String> {
+    let mut types = String::new();
+    let mut values = String::new();
+    let mut body = String::new();
+    let mut count = 0;
+    for segment in split_template(template)? {
+        match segment {
+            Segment::Literal(literal) => {
+                let token = byte_string(&literal);
+                _ = writeln!(
+                    body,
+                    "let ((), remainder) = __framework::parsers::token({token}).parse(remainder)?;"
+                );
+            }
+            Segment::Placeholder(kind, modifier) => {
+                let (ty, parser) = placeholder_parser(kind, modifier)?;
+                let name = format!("v{count}");
+                count += 1;
+                _ = writeln!(
+                    body,
+                    "let ({name}, remainder) = {parser}.parse(remainder)?;"
+                );
+                _ = write!(types, "{ty}, ");
+                _ = write!(values, "{name}, ");
+            }
+        }
+    }
+
+    Ok(format!(
+        "{{
+            #[derive(Debug, Clone, Copy)]
+            struct FormatParser;
+            impl<'s> __framework::parsers::Parser<'s> for FormatParser {{
+                type Output = ({types});
+
+                fn parse(
+                    &self,
+                    input: &'s [u8],
+                ) -> __framework::parsers::ParseResult<'s, Self::Output> {{
+                    #[allow(unused_imports)]
+                    use __framework::parsers::{{Parser, ParserCombiExt}};
+                    let remainder = input;
+                    {body}
+                    Ok((({values}), remainder))
+                }}
+            }}
+            FormatParser
+        }}"
+    ))
+}
@@ -0,0 +1,92 @@
+//! Procedural macros used by the framework. These are not meant to be used
+//! directly, instead use the wrappers exposed by `advent_of_code_framework`,
+//! which pass along the path to the framework crate as the first argument.
+
+use proc_macro::{Delimiter, Group, Literal, TokenStream, TokenTree};
+
+mod format;
+mod literal;
+mod pattern;
+
+/// Placeholder for the path to the framework crate in generated code, it is
+/// substituted by the path passed in by the wrapper macro.
+const FRAMEWORK: &str = "__framework";
+
+#[proc_macro]
+pub fn parse_format(input: TokenStream) -> TokenStream {
+    expand(input, format::expand)
+}
+
+#[proc_macro]
+pub fn pat(input: TokenStream) -> TokenStream {
+    expand(input, pattern::expand)
+}
+
+fn expand(input: TokenStream, f: impl FnOnce(&[u8]) -> Result<String, String>) -> TokenStream {
+    let (framework, literal) = match split_arguments(input) {
+        Ok(arguments) => arguments,
+        Err(message) => return compile_error(&message),
+    };
+    let result = literal::parse_string(&literal)
+        .ok_or_else(|| "expected a string literal".to_owned())
+        .and_then(|value| f(&value));
+    match result {
+        Ok(code) => substitute(code.parse().unwrap(), &framework),
+        Err(message) => compile_error(&message),
+    }
+}
+
+/// Splits the input into the path to the framework crate, and the literal.
+fn split_arguments(input: TokenStream) -> Result<(TokenStream, Literal), String> {
+    let mut framework = TokenStream::new();
+    let mut tokens = input.into_iter();
+    for token in tokens.by_ref() {
+        match token {
+            TokenTree::Punct(punct) if punct.as_char() == ',' => break,
+            token => framework.extend(Some(token)),
+        }
+    }
+    let literal = match tokens.next() {
+        Some(TokenTree::Literal(literal)) => literal,
+        // Literals forwarded by a declarative macro are wrapped in a group
+        // without delimiters.
+        Some(TokenTree::Group(group)) if group.delimiter() == Delimiter::None => {
+            match group.stream().into_iter().next() {
+                Some(TokenTree::Literal(literal)) => literal,
+                _ => return Err("expected a string literal".to_owned()),
+            }
+        }
+        _ => return Err("expected a string literal".to_owned()),
+    };
+    if tokens.next().is_some() {
+        return Err("unexpected tokens after the string literal".to_owned());
+    }
+    Ok((framework, literal))
+}
+
+fn substitute(stream: TokenStream, framework: &TokenStream) -> TokenStream {
+    stream
+        .into_iter()
+        .map(|token| match token {
+            TokenTree::Ident(ident) if ident.to_string() == FRAMEWORK => framework.clone(),
+            TokenTree::Group(group) => {
+                let mut new_group =
+                    Group::new(group.delimiter(), substitute(group.stream(), framework));
+                new_group.set_span(group.span());
+                TokenTree::Group(new_group).into()
+            }
+            token => token.into(),
+        })
+        .collect()
+}
+
+fn compile_error(message: &str) -> TokenStream {
+    format!("::core::compile_error!({message:?})")
+        .parse()
+        .unwrap()
+}
+
+/// Formats bytes as a byte string literal.
+fn byte_string(bytes: &[u8]) -> String {
+    format!("b\"{}\"", bytes.escape_ascii())
+}
@@ -0,0 +1,51 @@
+use proc_macro::Literal;
+
+/// Parses a (byte) string literal, or raw (byte) string literal, returning
+/// its unescaped contents.
+pub fn parse_string(literal: &Literal) -> Option<Vec<u8>> {
+    let repr = literal.to_string();
+    let repr = repr.strip_prefix('b').unwrap_or(&repr);
+    if let Some(raw) = repr.strip_prefix('r') {
+        let hashes = raw.len() - raw.trim_start_matches('#').len();
+        let body = raw.get(hashes..raw.len() - hashes)?;
+        let body = body.strip_prefix('"')?.strip_suffix('"')?;
+        return Some(body.as_bytes().to_vec());
+    }
+
+    let body = repr.strip_prefix('"')?.strip_suffix('"')?;
+    let mut result = Vec::with_capacity(body.len());
+    let mut chars = body.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            let mut buffer = [0; 4];
+            result.extend_from_slice(c.encode_utf8(&mut buffer).as_bytes());
+            continue;
+        }
+        match chars.next()? {
+            'n' => result.push(b'\n'),
+            'r' => result.push(b'\r'),
+            't' => result.push(b'\t'),
+            '0' => result.push(b'\0'),
+            '\\' => result.push(b'\\'),
+            '\'' => result.push(b'\''),
+            '"' => result.push(b'"'),
+            'x' => {
+                let digits = chars.as_str().get(..2)?;
+                result.push(u8::from_str_radix(digits, 16).ok()?);
+                chars.nth(1);
+            }
+            'u' => {
+                let rest = chars.as_str().strip_prefix('{')?;
+                let end = rest.find('}')?;
+                let c = char::from_u32(u32::from_str_radix(&rest[..end], 16).ok()?)?;
+                let mut buffer = [0; 4];
+                result.extend_from_slice(c.encode_utf8(&mut buffer).as_bytes());
+                chars = rest[end + 1..].chars();
+            }
+            // Line continuation, skips the newline and leading whitespace
+            '\n' => chars = chars.as_str().trim_start().chars(),
+            _ => return None,
+        }
+    }
+    Some(result)
+}
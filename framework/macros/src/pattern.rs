@@ -0,0 +1,289 @@
+use std::fmt::Write;
+
+/// A set of bytes, indexed by the byte value.
+type ByteSet = [bool; 256];
+
+struct Element {
+    set: ByteSet,
+    min: usize,
+    max: Option<usize>,
+}
+
+fn set_of(predicate: impl Fn(u8) -> bool) -> ByteSet {
+    std::array::from_fn(|c| predicate(c as u8))
+}
+
+/// The bytes with a special meaning in patterns, which match themselves when
+/// they are escaped.
+const METACHARACTERS: &[u8] = b"\\[]-^.?*+{}()|";
+
+/// What an escape sequence stands for, either a class such as `\d`, or a
+/// single byte such as `\n` or `\[`.
+#[derive(Debug)]
+enum Escape {
+    Class(fn(u8) -> bool),
+    Byte(u8),
+}
+
+/// Parses the byte after a `\`, rejecting unknown escapes, so that they can't
+/// be mistaken for the byte itself.
+fn parse_escape(c: u8) -> Result<Escape, String> {
+    Ok(match c {
+        b'd' => Escape::Class(|c| c.is_ascii_digit()),
+        b'w' => Escape::Class(|c| c.is_ascii_alphanumeric() || c == b'_'),
+        b's' => Escape::Class(|c| c.is_ascii_whitespace()),
+        b'n' => Escape::Byte(b'\n'),
+        b't' => Escape::Byte(b'\t'),
+        c if METACHARACTERS.contains(&c) => Escape::Byte(c),
+        c => {
+            return Err(format!(
+                "unknown escape `\\{}` in pattern",
+                c.escape_ascii()
+            ))
+        }
+    })
+}
+
+/// Parses a class such as `[^a-z_]`, starting after the `[`, returns the set
+/// and the number of bytes that were consumed, including the closing `]`.
+fn parse_class(pattern: &[u8]) -> Result<(ByteSet, usize), String> {
+    let (negated, mut index) = match pattern.first() {
+        Some(b'^') => (true, 1),
+        _ => (false, 0),
+    };
+    let mut set = [false; 256];
+    loop {
+        let (first, length) = match pattern.get(index..) {
+            None | Some([]) => return Err("unterminated `[` in pattern".to_owned()),
+            Some([b']', ..]) => break,
+            Some([b'\\', c, ..]) => match parse_escape(*c)? {
+                Escape::Class(escaped) => {
+                    set.iter_mut()
+                        .zip(set_of(escaped))
+                        .for_each(|(a, b)| *a |= b);
+                    index += 2;
+                    continue;
+                }
+                Escape::Byte(c) => (c, 2),
+            },
+            Some([b'\\']) => return Err("unterminated escape in pattern".to_owned()),
+            Some([c, ..]) => (*c, 1),
+        };
+        index += length;
+        let last = match pattern.get(index..) {
+            Some([b'-', b'\\', c, ..]) => match parse_escape(*c)? {
+                Escape::Byte(last) => {
+                    index += 3;
+                    last
+                }
+                Escape::Class(_) => first,
+            },
+            Some([b'-', c, ..]) if *c != b']' && *c != b'\\' => {
+                index += 2;
+                *c
+            }
+            _ => first,
+        };
+        if last < first {
+            return Err(format!(
+                "invalid range `{}-{}` in pattern",
+                first.escape_ascii(),
+                last.escape_ascii()
+            ));
+        }
+        set[first as usize..=last as usize].fill(true);
+    }
+    if negated {
+        set.iter_mut().for_each(|member| *member = !*member);
+    }
+    Ok((set, index + 1))
+}
+
+/// Parses a number in a `{n,m}` quantifier.
+fn parse_count(text: &[u8]) -> Result<usize, String> {
+    std::str::from_utf8(text)
+        .ok()
+        .and_then(|text| text.trim().parse().ok())
+        .ok_or_else(|| format!("invalid count `{}` in pattern", text.escape_ascii()))
+}
+
+/// Parses an optional quantifier, returns the minimum and maximum number of
+/// repetitions, and the number of bytes that were consumed.
+fn parse_quantifier(pattern: &[u8]) -> Result<(usize, Option<usize>, usize), String> {
+    Ok(match pattern.first() {
+        Some(b'?') => (0, Some(1), 1),
+        Some(b'*') => (0, None, 1),
+        Some(b'+') => (1, None, 1),
+        Some(b'{') => {
+            let length = pattern
+                .iter()
+                .position(|&c| c == b'}')
+                .ok_or("unterminated `{` in pattern")?;
+            let inner = &pattern[1..length];
+            let (min, max) = match inner.iter().position(|&c| c == b',') {
+                None => {
+                    let count = parse_count(inner)?;
+                    (count, Some(count))
+                }
+                Some(comma) if comma + 1 == inner.len() => (parse_count(&inner[..comma])?, None),
+                Some(comma) => (
+                    parse_count(&inner[..comma])?,
+                    Some(parse_count(&inner[comma + 1..])?),
+                ),
+            };
+            if max.is_some_and(|max| max < min || max == 0) {
+                return Err(format!(
+                    "invalid quantifier `{}` in pattern",
+                    pattern[..=length].escape_ascii()
+                ));
+            }
+            (min, max, length + 1)
+        }
+        _ => (1, Some(1), 0),
+    })
+}
+
+fn parse_pattern(pattern: &[u8]) -> Result<Vec<Element>, String> {
+    let mut elements = Vec::new();
+    let mut index = 0;
+    while index < pattern.len() {
+        let (set, length) = match &pattern[index..] {
+            [b'[', rest @ ..] => {
+                let (set, length) = parse_class(rest)?;
+                (set, length + 1)
+            }
+            [b'\\', c, ..] => match parse_escape(*c)? {
+                Escape::Class(class) => (set_of(class), 2),
+                Escape::Byte(c) => (set_of(|other| other == c), 2),
+            },
+            [b'\\'] => return Err("unterminated escape in pattern".to_owned()),
+            [b'.', ..] => (set_of(|c| c != b'\n'), 1),
+            [c @ (b'?' | b'*' | b'+' | b'{'), ..] => {
+                return Err(format!(
+                    "quantifier `{}` does not follow anything in pattern",
+                    *c as char
+                ))
+            }
+            [c @ (b'(' | b')' | b'|' | b']' | b'}'), ..] => {
+                return Err(format!(
+                    "unsupported `{}` in pattern, escape it to match it literally",
+                    *c as char
+                ))
+            }
+            [c, ..] => (set_of(|other| other == *c), 1),
+            [] => unreachable!(),
+        };
+        index += length;
+        if !set.contains(&true) {
+            return Err("pattern contains a class that matches nothing".to_owned());
+        }
+        let (min, max, length) = parse_quantifier(&pattern[index..])?;
+        index += length;
+        elements.push(Element { set, min, max });
+    }
+    if elements.is_empty() {
+        return Err("pattern is empty".to_owned());
+    }
+    Ok(elements)
+}
+
+/// Formats a set of bytes as a match pattern, such as `0x30..=0x39 | 0x5f`.
+fn match_pattern(set: &ByteSet) -> String {
+    if set.iter().all(|&member| member) {
+        return "_".to_owned();
+    }
+    let mut pattern = String::new();
+    let mut index = 0;
+    while index < set.len() {
+        if !set[index] {
+            index += 1;
+            continue;
+        }
+        let start = index;
+        while index < set.len() && set[index] {
+            index += 1;
+        }
+        if !pattern.is_empty() {
+            pattern.push_str(" | ");
+        }
+        match index - start {
+            1 => _ = write!(pattern, "{start:#04x}"),
+            _ => _ = write!(pattern, "{start:#04x}..={:#04x}", index - 1),
+        }
+    }
+    pattern
+}
+
+pub fn expand(pattern: &[u8]) -> Result<String, String> {
+    let mut body = String::new();
+    for Element { set, min, max } in parse_pattern(pattern)? {
+        let set = match_pattern(&set);
+        if min > 0 || max.is_some() {
+            body.push_str("let start = index;\n");
+        }
+        let bound = match max {
+            Some(max) => format!("index - start < {max} && "),
+            None => String::new(),
+        };
+        _ = writeln!(
+            body,
+            "while {bound}index < input.len() && matches!(input[index], {set}) {{
+                index += 1;
+            }}"
+        );
+        if min > 0 {
+            _ = writeln!(
+                body,
+                "if index - start < {min} {{
+                    return Err((
+                        if index == input.len() {{
+                            __framework::parsers::ParseError::EmptyInput
+                        }} else {{
+                            __framework::parsers::ParseError::UnexpectedChar
+                        }},
+                        &input[index..],
+                    ));
+                }}"
+            );
+        }
+    }
+
+    Ok(format!(
+        "{{
+            #[derive(Debug, Clone, Copy)]
+            struct PatternParser;
+            impl<'s> __framework::parsers::Parser<'s> for PatternParser {{
+                type Output = &'s __framework::astr::AStr;
+
+                fn parse(
+                    &self,
+                    input: &'s [u8],
+                ) -> __framework::parsers::ParseResult<'s, Self::Output> {{
+                    let mut index = 0;
+                    {body}
+                    Ok((&input[..index], &input[index..]))
+                }}
+            }}
+            PatternParser
+        }}"
+    ))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn escapes() {
+        assert!(matches!(parse_escape(b'n'), Ok(Escape::Byte(b'\n'))));
+        assert!(matches!(parse_escape(b'['), Ok(Escape::Byte(b'['))));
+        assert!(matches!(parse_escape(b'\\'), Ok(Escape::Byte(b'\\'))));
+        assert!(matches!(parse_escape(b'd'), Ok(Escape::Class(class)) if class(b'7')));
+        for c in *b"rDWSax" {
+            assert!(parse_escape(c).is_err(), "`\\{}` was accepted", c as char);
+        }
+        assert!(parse_pattern(br"[a-\d]").is_ok());
+        assert!(parse_pattern(br"[\r]").is_err());
+        assert!(parse_pattern(br"a-\D").is_err());
+    }
+}
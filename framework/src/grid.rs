@@ -1,13 +1,29 @@
 use crate::parsers::ParseError;
 use std::{
-    ops::{Index, IndexMut},
+    iter::StepBy,
+    ops::{Index, IndexMut, Range},
     slice::{Iter, IterMut},
 };
 
+mod bits;
+mod grid3;
+mod search;
+mod sparse;
+mod wrap;
+
+pub use bits::{BitGrid, BitGridBuilder};
+pub use grid3::{VecGrid3, ORTHOGONAL_3D};
+pub use sparse::{SparseGrid, SparseGridBuilder};
+pub use wrap::{Wrap, Wrapped, WrappingGrid};
+
 type Vec2 = crate::vecs::Vec2<usize>;
 
-/// A 2D grid
-pub trait Grid<T>: Sized + IndexMut<Self::Indexer> {
+/// The cells of a single column, from top to bottom.
+pub type Column<'g, T> = StepBy<Iter<'g, T>>;
+
+/// A 2D grid, grids that can hand out mutable references to their cells also
+/// implement `IndexMut`.
+pub trait Grid<T>: Sized + Index<Self::Indexer> {
     type Indexer;
     type Builder: GridBuilder<T, Output = Self>;
 }
@@ -21,7 +37,18 @@ pub trait GridBuilder<T> {
     fn finish(self) -> Result<Self::Output, ParseError>;
 }
 
-#[derive(Debug, Clone)]
+/// A builder that can also build grids from rows of different lengths.
+pub trait RaggedGridBuilder<T>: GridBuilder<T> {
+    /// Creates a builder that allows rows of different lengths, rows shorter
+    /// than the longest row are padded with `padding`.
+    fn ragged(padding: T) -> Self;
+    /// Finishes a builder created by `ragged`, also returns the extent of each
+    /// row, from its first cell that isn't `padding` up to the end of the cells
+    /// that were pushed on it.
+    fn finish_ragged(self) -> Result<(Self::Output, Vec<Range<usize>>), ParseError>;
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub struct VecGrid<T> {
     size: Vec2,
     data: Vec<T>,
@@ -32,6 +59,14 @@ pub struct VecGridBuilder<T> {
     width: Option<usize>,
     x: usize,
     data: Vec<T>,
+    ragged: Option<Ragged<T>>,
+}
+
+#[derive(Debug, Clone)]
+struct Ragged<T> {
+    padding: T,
+    /// The number of cells on each completed row.
+    row_lengths: Vec<usize>,
 }
 
 impl<T> Grid<T> for VecGrid<T> {
@@ -142,6 +177,139 @@ impl<T> VecGrid<T> {
         }
         str
     }
+
+    pub fn row(&self, y: usize) -> &[T] {
+        assert!(y < self.size.y);
+        &self.data[y * self.size.x..(y + 1) * self.size.x]
+    }
+
+    pub fn row_mut(&mut self, y: usize) -> &mut [T] {
+        assert!(y < self.size.y);
+        &mut self.data[y * self.size.x..(y + 1) * self.size.x]
+    }
+
+    pub fn rows(&self) -> impl DoubleEndedIterator<Item = &[T]> + ExactSizeIterator {
+        (0..self.size.y).map(|y| self.row(y))
+    }
+
+    pub fn rows_mut(&mut self) -> RowsMut<'_, T> {
+        RowsMut {
+            data: &mut self.data,
+            stride: self.size.x,
+            width: self.size.x,
+            rows: self.size.y,
+        }
+    }
+
+    pub fn column(&self, x: usize) -> Column<'_, T> {
+        assert!(x < self.size.x);
+        self.data
+            .get(x..)
+            .unwrap_or(&[])
+            .iter()
+            .step_by(self.size.x)
+    }
+
+    pub fn columns(&self) -> impl DoubleEndedIterator<Item = Column<'_, T>> + ExactSizeIterator {
+        (0..self.size.x).map(|x| self.column(x))
+    }
+
+    /// Borrows the rectangle of `size` cells, with its top left corner at
+    /// `origin`.
+    ///
+    /// # Panics
+    /// When the rectangle does not fit in the grid.
+    pub fn view(&self, origin: impl Into<Vec2>, size: impl Into<Vec2>) -> GridView<'_, T> {
+        let size = size.into();
+        let range = view_range(self.size.x, self.size, origin.into(), size);
+        GridView {
+            data: &self.data[range],
+            stride: self.size.x,
+            size,
+        }
+    }
+
+    /// Mutably borrows the rectangle of `size` cells, with its top left corner
+    /// at `origin`.
+    ///
+    /// # Panics
+    /// When the rectangle does not fit in the grid.
+    pub fn view_mut(
+        &mut self,
+        origin: impl Into<Vec2>,
+        size: impl Into<Vec2>,
+    ) -> GridViewMut<'_, T> {
+        let size = size.into();
+        let range = view_range(self.size.x, self.size, origin.into(), size);
+        GridViewMut {
+            data: &mut self.data[range],
+            stride: self.size.x,
+            size,
+        }
+    }
+
+    /// Creates a grid of `size`, where each cell is copied from the position
+    /// `source` maps it to.
+    fn remap(&self, size: Vec2, source: impl Fn(Vec2) -> Vec2) -> VecGrid<T>
+    where
+        T: Clone,
+    {
+        VecGrid::new(size, |p| self[source(p)].clone())
+    }
+
+    /// Mirrors the grid along its main diagonal, swapping rows and columns.
+    pub fn transpose(&self) -> VecGrid<T>
+    where
+        T: Clone,
+    {
+        self.remap(self.size.transpose(), Vec2::transpose)
+    }
+
+    /// Rotates the grid a quarter turn clockwise, as it appears when printed
+    /// with `stringify`.
+    pub fn rotate_90(&self) -> VecGrid<T>
+    where
+        T: Clone,
+    {
+        let height = self.size.y;
+        self.remap(self.size.transpose(), |p| Vec2::new(p.y, height - 1 - p.x))
+    }
+
+    pub fn rotate_180(&self) -> VecGrid<T>
+    where
+        T: Clone,
+    {
+        let size = self.size;
+        self.remap(size, |p| Vec2::new(size.x - 1 - p.x, size.y - 1 - p.y))
+    }
+
+    /// Rotates the grid a quarter turn counterclockwise, as it appears when
+    /// printed with `stringify`.
+    pub fn rotate_270(&self) -> VecGrid<T>
+    where
+        T: Clone,
+    {
+        let width = self.size.x;
+        self.remap(self.size.transpose(), |p| Vec2::new(width - 1 - p.y, p.x))
+    }
+
+    /// Reverses the order of the cells in each row.
+    pub fn flip_x(&self) -> VecGrid<T>
+    where
+        T: Clone,
+    {
+        let width = self.size.x;
+        self.remap(self.size, |p| Vec2::new(width - 1 - p.x, p.y))
+    }
+
+    /// Reverses the order of the rows.
+    pub fn flip_y(&self) -> VecGrid<T>
+    where
+        T: Clone,
+    {
+        let height = self.size.y;
+        self.remap(self.size, |p| Vec2::new(p.x, height - 1 - p.y))
+    }
 }
 
 impl<T, V: Into<Vec2>> Index<V> for VecGrid<T> {
@@ -165,6 +333,333 @@ impl<T, V: Into<Vec2>> IndexMut<V> for VecGrid<T> {
     }
 }
 
+/// Returns the range of the cells spanned by a view of `size` at `origin`, in
+/// the cells of a grid of `bounds` whose rows are `stride` cells apart.
+fn view_range(stride: usize, bounds: Vec2, origin: Vec2, size: Vec2) -> Range<usize> {
+    assert!(
+        origin.x + size.x <= bounds.x && origin.y + size.y <= bounds.y,
+        "view of {size:?} at {origin:?} does not fit in {bounds:?}"
+    );
+    if size.x == 0 || size.y == 0 {
+        return 0..0;
+    }
+    let start = origin.y * stride + origin.x;
+    start..start + (size.y - 1) * stride + size.x
+}
+
+/// A borrowed rectangular part of a `VecGrid`, created by `VecGrid::view`.
+/// Positions are relative to the top left corner of the view.
+#[derive(Debug)]
+pub struct GridView<'g, T> {
+    /// The cells from the top left to the bottom right corner of the view,
+    /// including the cells outside of the view in between.
+    data: &'g [T],
+    stride: usize,
+    size: Vec2,
+}
+
+/// A mutably borrowed rectangular part of a `VecGrid`, created by
+/// `VecGrid::view_mut`. Positions are relative to the top left corner of the
+/// view.
+#[derive(Debug)]
+pub struct GridViewMut<'g, T> {
+    data: &'g mut [T],
+    stride: usize,
+    size: Vec2,
+}
+
+impl<'g, T> Clone for GridView<'g, T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<'g, T> Copy for GridView<'g, T> {}
+
+impl<'g, T> GridView<'g, T> {
+    pub fn size(&self) -> Vec2 {
+        self.size
+    }
+
+    pub fn width(&self) -> usize {
+        self.size.x
+    }
+
+    pub fn height(&self) -> usize {
+        self.size.y
+    }
+
+    #[inline]
+    pub fn get<V: Into<Vec2>>(&self, index: V) -> Option<&'g T> {
+        let index = index.into();
+        if index.x < self.size.x && index.y < self.size.y {
+            Some(&self.data[index.y * self.stride + index.x])
+        } else {
+            None
+        }
+    }
+
+    pub fn row(&self, y: usize) -> &'g [T] {
+        assert!(y < self.size.y);
+        match self.size.x {
+            0 => &[],
+            width => &self.data[y * self.stride..y * self.stride + width],
+        }
+    }
+
+    pub fn rows(&self) -> impl DoubleEndedIterator<Item = &'g [T]> + ExactSizeIterator {
+        let view = *self;
+        (0..self.size.y).map(move |y| view.row(y))
+    }
+
+    pub fn column(&self, x: usize) -> Column<'g, T> {
+        assert!(x < self.size.x);
+        self.data
+            .get(x..)
+            .unwrap_or(&[])
+            .iter()
+            .step_by(self.stride)
+    }
+
+    pub fn columns(&self) -> impl DoubleEndedIterator<Item = Column<'g, T>> + ExactSizeIterator {
+        let view = *self;
+        (0..self.size.x).map(move |x| view.column(x))
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (Vec2, &'g T)> {
+        self.rows().enumerate().flat_map(|(y, row)| {
+            row.iter()
+                .enumerate()
+                .map(move |(x, cell)| (Vec2::new(x, y), cell))
+        })
+    }
+
+    /// Borrows a rectangle within this view, see `VecGrid::view`.
+    pub fn view(&self, origin: impl Into<Vec2>, size: impl Into<Vec2>) -> GridView<'g, T> {
+        let size = size.into();
+        let range = view_range(self.stride, self.size, origin.into(), size);
+        GridView {
+            data: &self.data[range],
+            stride: self.stride,
+            size,
+        }
+    }
+
+    pub fn stringify(&self, mut to_char: impl FnMut(&T) -> char) -> String {
+        let rows = self
+            .rows()
+            .map(|row| row.iter().map(&mut to_char).collect::<String>());
+        rows.collect::<Vec<_>>().join("\n")
+    }
+
+    pub fn to_grid(&self) -> VecGrid<T>
+    where
+        T: Clone,
+    {
+        VecGrid::new(self.size, |p| self[p].clone())
+    }
+}
+
+impl<'g, T> GridViewMut<'g, T> {
+    pub fn as_view(&self) -> GridView<'_, T> {
+        GridView {
+            data: self.data,
+            stride: self.stride,
+            size: self.size,
+        }
+    }
+
+    pub fn size(&self) -> Vec2 {
+        self.size
+    }
+
+    pub fn width(&self) -> usize {
+        self.size.x
+    }
+
+    pub fn height(&self) -> usize {
+        self.size.y
+    }
+
+    #[inline]
+    pub fn get<V: Into<Vec2>>(&self, index: V) -> Option<&T> {
+        self.as_view().get(index)
+    }
+
+    #[inline]
+    pub fn get_mut<V: Into<Vec2>>(&mut self, index: V) -> Option<&mut T> {
+        let index = index.into();
+        if index.x < self.size.x && index.y < self.size.y {
+            Some(&mut self.data[index.y * self.stride + index.x])
+        } else {
+            None
+        }
+    }
+
+    pub fn row(&self, y: usize) -> &[T] {
+        self.as_view().row(y)
+    }
+
+    pub fn row_mut(&mut self, y: usize) -> &mut [T] {
+        assert!(y < self.size.y);
+        match self.size.x {
+            0 => &mut [],
+            width => &mut self.data[y * self.stride..y * self.stride + width],
+        }
+    }
+
+    pub fn rows(&self) -> impl DoubleEndedIterator<Item = &[T]> + ExactSizeIterator {
+        self.as_view().rows()
+    }
+
+    pub fn rows_mut(&mut self) -> RowsMut<'_, T> {
+        RowsMut {
+            data: self.data,
+            stride: self.stride,
+            width: self.size.x,
+            rows: self.size.y,
+        }
+    }
+
+    pub fn column(&self, x: usize) -> Column<'_, T> {
+        self.as_view().column(x)
+    }
+
+    pub fn columns(&self) -> impl DoubleEndedIterator<Item = Column<'_, T>> + ExactSizeIterator {
+        self.as_view().columns()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (Vec2, &T)> {
+        self.as_view().iter()
+    }
+
+    pub fn iter_mut(&mut self) -> impl Iterator<Item = (Vec2, &mut T)> {
+        self.rows_mut().enumerate().flat_map(|(y, row)| {
+            row.iter_mut()
+                .enumerate()
+                .map(move |(x, cell)| (Vec2::new(x, y), cell))
+        })
+    }
+
+    /// Borrows a rectangle within this view, see `VecGrid::view`.
+    pub fn view(&self, origin: impl Into<Vec2>, size: impl Into<Vec2>) -> GridView<'_, T> {
+        self.as_view().view(origin, size)
+    }
+
+    /// Mutably borrows a rectangle within this view, see `VecGrid::view_mut`.
+    pub fn view_mut(
+        &mut self,
+        origin: impl Into<Vec2>,
+        size: impl Into<Vec2>,
+    ) -> GridViewMut<'_, T> {
+        let size = size.into();
+        let range = view_range(self.stride, self.size, origin.into(), size);
+        GridViewMut {
+            data: &mut self.data[range],
+            stride: self.stride,
+            size,
+        }
+    }
+
+    pub fn stringify(&self, to_char: impl FnMut(&T) -> char) -> String {
+        self.as_view().stringify(to_char)
+    }
+
+    pub fn to_grid(&self) -> VecGrid<T>
+    where
+        T: Clone,
+    {
+        self.as_view().to_grid()
+    }
+}
+
+/// Iterator over the rows of a grid or view, created by `rows_mut`. Unlike
+/// `chunks_mut`, this yields every row, even when they are empty.
+#[derive(Debug)]
+pub struct RowsMut<'g, T> {
+    /// The cells from the start of the first remaining row, to the end of the
+    /// last remaining row, or further.
+    data: &'g mut [T],
+    stride: usize,
+    width: usize,
+    rows: usize,
+}
+
+impl<'g, T> Iterator for RowsMut<'g, T> {
+    type Item = &'g mut [T];
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.rows == 0 {
+            return None;
+        }
+        self.rows -= 1;
+        if self.width == 0 {
+            return Some(&mut []);
+        }
+        let (row, remainder) = std::mem::take(&mut self.data).split_at_mut(self.width);
+        if self.rows != 0 {
+            self.data = &mut remainder[self.stride - self.width..];
+        }
+        Some(row)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.rows, Some(self.rows))
+    }
+}
+
+impl<'g, T> DoubleEndedIterator for RowsMut<'g, T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.rows == 0 {
+            return None;
+        }
+        self.rows -= 1;
+        if self.width == 0 {
+            return Some(&mut []);
+        }
+        let data = std::mem::take(&mut self.data);
+        let (remainder, row) = data.split_at_mut(self.rows * self.stride);
+        self.data = remainder;
+        Some(&mut row[..self.width])
+    }
+}
+
+impl<'g, T> ExactSizeIterator for RowsMut<'g, T> {}
+
+impl<'g, T, V: Into<Vec2>> Index<V> for GridView<'g, T> {
+    type Output = T;
+
+    #[inline]
+    fn index(&self, index: V) -> &Self::Output {
+        let index = index.into();
+        assert!(index.x < self.size.x);
+        assert!(index.y < self.size.y);
+        &self.data[index.y * self.stride + index.x]
+    }
+}
+
+impl<'g, T, V: Into<Vec2>> Index<V> for GridViewMut<'g, T> {
+    type Output = T;
+
+    #[inline]
+    fn index(&self, index: V) -> &Self::Output {
+        let index = index.into();
+        assert!(index.x < self.size.x);
+        assert!(index.y < self.size.y);
+        &self.data[index.y * self.stride + index.x]
+    }
+}
+
+impl<'g, T, V: Into<Vec2>> IndexMut<V> for GridViewMut<'g, T> {
+    #[inline]
+    fn index_mut(&mut self, index: V) -> &mut Self::Output {
+        let index = index.into();
+        assert!(index.x < self.size.x);
+        assert!(index.y < self.size.y);
+        &mut self.data[index.y * self.stride + index.x]
+    }
+}
+
 impl<T> GridBuilder<T> for VecGridBuilder<T> {
     type Output = VecGrid<T>;
     fn new() -> Self {
@@ -172,6 +667,7 @@ impl<T> GridBuilder<T> for VecGridBuilder<T> {
             width: None,
             x: 0,
             data: Vec::new(),
+            ragged: None,
         }
     }
 
@@ -181,7 +677,7 @@ impl<T> GridBuilder<T> for VecGridBuilder<T> {
 
     fn push_cell(&mut self, cell: T) -> Result<(), ParseError> {
         if let Some(width) = self.width {
-            if self.x >= width {
+            if self.x >= width && self.ragged.is_none() {
                 return Err(ParseError::GridCellAfterEndOfRowReached);
             }
         }
@@ -191,7 +687,10 @@ impl<T> GridBuilder<T> for VecGridBuilder<T> {
     }
 
     fn advance_next_line(&mut self) -> Result<(), ParseError> {
-        if let Some(width) = self.width {
+        if let Some(ragged) = &mut self.ragged {
+            ragged.row_lengths.push(self.x);
+            self.width = Some(self.width.unwrap_or(0).max(self.x));
+        } else if let Some(width) = self.width {
             if self.x != width {
                 return Err(ParseError::GridIncompleteRow);
             }
@@ -202,7 +701,13 @@ impl<T> GridBuilder<T> for VecGridBuilder<T> {
         Ok(())
     }
 
+    /// # Panics
+    /// When the builder was created by `ragged`, use `finish_ragged` instead.
     fn finish(mut self) -> Result<Self::Output, ParseError> {
+        assert!(
+            self.ragged.is_none(),
+            "a ragged grid must be finished with finish_ragged"
+        );
         if self.width.is_none() {
             self.advance_next_line()?;
         }
@@ -219,6 +724,52 @@ impl<T> GridBuilder<T> for VecGridBuilder<T> {
     }
 }
 
+impl<T: Clone + PartialEq> RaggedGridBuilder<T> for VecGridBuilder<T> {
+    fn ragged(padding: T) -> Self {
+        VecGridBuilder {
+            ragged: Some(Ragged {
+                padding,
+                row_lengths: Vec::new(),
+            }),
+            ..Self::new()
+        }
+    }
+
+    fn finish_ragged(mut self) -> Result<(Self::Output, Vec<Range<usize>>), ParseError> {
+        let row_lengths = &self
+            .ragged
+            .as_ref()
+            .expect("grid is not ragged")
+            .row_lengths;
+        if self.x != 0 || row_lengths.is_empty() {
+            self.advance_next_line()?;
+        }
+        let width = self.width.unwrap();
+        let Ragged {
+            padding,
+            row_lengths,
+        } = self.ragged.unwrap();
+        let mut cells = self.data.into_iter();
+        let mut data = Vec::with_capacity(width * row_lengths.len());
+        let mut extents = Vec::with_capacity(row_lengths.len());
+        for &length in &row_lengths {
+            let row_start = data.len();
+            data.extend(cells.by_ref().take(length));
+            let start = data[row_start..]
+                .iter()
+                .position(|cell| *cell != padding)
+                .unwrap_or(length);
+            extents.push(start..length);
+            data.resize(row_start + width, padding.clone());
+        }
+        let grid = VecGrid {
+            size: Vec2::new(width, row_lengths.len()),
+            data,
+        };
+        Ok((grid, extents))
+    }
+}
+
 impl<T> IntoIterator for VecGrid<T> {
     type IntoIter = VecGridIntoIter<T>;
     type Item = (Vec2, T);
@@ -280,3 +831,98 @@ impl<'g, T> Iterator for VecGridIterMut<'g, T> {
     type Item = (Vec2, &'g mut T);
     impl_iter!();
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn letters(size: (usize, usize)) -> VecGrid<char> {
+        VecGrid::new(size, |p| (b'a' + (p.y * size.0 + p.x) as u8) as char)
+    }
+
+    #[test]
+    #[rustfmt::skip]
+    fn transforms() {
+        let grid = letters((3, 2));
+        let stringify = |g: VecGrid<char>| g.stringify(|&c| c);
+        assert_eq!("abc\ndef",      stringify(grid.clone()));
+        assert_eq!("ad\nbe\ncf",    stringify(grid.transpose()));
+        assert_eq!("da\neb\nfc",    stringify(grid.rotate_90()));
+        assert_eq!("fed\ncba",      stringify(grid.rotate_180()));
+        assert_eq!("cf\nbe\nad",    stringify(grid.rotate_270()));
+        assert_eq!("cba\nfed",      stringify(grid.flip_x()));
+        assert_eq!("def\nabc",      stringify(grid.flip_y()));
+        assert_eq!(grid,            grid.rotate_90().rotate_270());
+        assert_eq!(grid.rotate_90(), grid.transpose().flip_x());
+    }
+
+    #[test]
+    fn rows_and_columns() {
+        let grid = letters((3, 2));
+        assert_eq!(
+            vec![&['a', 'b', 'c'][..], &['d', 'e', 'f']],
+            grid.rows().collect::<Vec<_>>()
+        );
+        assert_eq!(
+            vec!["ad", "be", "cf"],
+            grid.columns()
+                .map(|c| c.collect::<String>())
+                .collect::<Vec<_>>()
+        );
+        assert_eq!("fc", grid.column(2).rev().collect::<String>());
+    }
+
+    #[test]
+    fn views() {
+        let mut grid = letters((4, 4));
+        let view = grid.view((1, 1), (2, 3));
+        assert_eq!("fg\njk\nno", view.stringify(|&c| c));
+        assert_eq!(Some(&'k'), view.get((1, 1)));
+        assert_eq!(None, view.get((2, 0)));
+        assert_eq!('n', view[(0, 2)]);
+        assert_eq!(
+            vec!["fjn", "gko"],
+            view.columns()
+                .map(|c| c.collect::<String>())
+                .collect::<Vec<_>>()
+        );
+        assert_eq!("k\no", view.view((1, 1), (1, 2)).stringify(|&c| c));
+        assert_eq!(
+            vec![(Vec2::new(0, 0), &'f'), (Vec2::new(1, 0), &'g')],
+            view.iter().take(2).collect::<Vec<_>>()
+        );
+
+        let mut view = grid.view_mut((2, 0), (2, 2));
+        view[(0, 0)] = 'X';
+        for (p, cell) in view.iter_mut().skip(1) {
+            *cell = (b'0' + (p.y * 2 + p.x) as u8) as char;
+        }
+        view.view_mut((0, 1), (1, 1))[(0, 0)] = 'Y';
+        assert_eq!("abX1\nefY3\nijkl\nmnop", grid.stringify(|&c| c));
+        let mut view = grid.view_mut((1, 1), (3, 2));
+        let mut rows = view.rows_mut();
+        assert_eq!(2, rows.len());
+        rows.next_back().unwrap()[0] = 'Z';
+        assert_eq!(&['f', 'Y', '3'][..], rows.next().unwrap());
+        assert_eq!(None, rows.next());
+        assert_eq!("abX1\nefY3\niZkl\nmnop", grid.stringify(|&c| c));
+    }
+
+    #[test]
+    fn empty_views() {
+        let mut grid = letters((3, 3));
+        for (origin, size) in [((1, 0), (0, 2)), ((1, 1), (2, 0)), ((3, 3), (0, 0))] {
+            let mut view = grid.view_mut(origin, size);
+            assert_eq!(size.1, view.rows().len());
+            assert!(view.rows().all(|row| row.is_empty()));
+            assert_eq!(
+                size.1,
+                view.rows_mut().rev().filter(|row| row.is_empty()).count()
+            );
+            assert_eq!(size.0, view.columns().filter(|c| c.len() == 0).count());
+            assert_eq!(None, view.get((0, 0)));
+        }
+        assert_eq!("\n", grid.view((2, 1), (0, 2)).stringify(|&c| c));
+        assert_eq!(3, grid.rows_mut().len());
+    }
+}
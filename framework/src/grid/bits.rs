@@ -0,0 +1,378 @@
+use super::{Grid, GridBuilder, RaggedGridBuilder, VecGrid, VecGridBuilder};
+use crate::offsets::{Neighbor, Offset};
+use crate::parsers::ParseError;
+use std::ops::{BitAnd, BitAndAssign, BitOr, BitOrAssign, BitXor, BitXorAssign, Index, Not, Range};
+
+type Vec2 = crate::vecs::Vec2<usize>;
+
+const WORD_BITS: usize = u64::BITS as usize;
+
+/// A grid of booleans, with each row packed into `u64` words, so that
+/// operations on whole rows or grids handle 64 cells at a time. Cell `x` of a
+/// row is bit `x % 64` of word `x / 64` of that row. The bits beyond the width
+/// of the grid are always zero.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct BitGrid {
+    size: Vec2,
+    words_per_row: usize,
+    data: Vec<u64>,
+}
+
+impl Grid<bool> for BitGrid {
+    type Indexer = Vec2;
+    type Builder = BitGridBuilder;
+}
+
+impl BitGrid {
+    pub fn new(size: impl Into<Vec2>, mut initializer: impl FnMut(Vec2) -> bool) -> BitGrid {
+        let size = size.into();
+        assert!(size.x > 0);
+        assert!(size.y > 0);
+        let mut grid = BitGrid::zeroed(size);
+        for y in 0..size.y {
+            for x in 0..size.x {
+                if initializer(Vec2::new(x, y)) {
+                    grid.data[y * grid.words_per_row + x / WORD_BITS] |= 1 << (x % WORD_BITS);
+                }
+            }
+        }
+        grid
+    }
+
+    fn zeroed(size: Vec2) -> BitGrid {
+        let words_per_row = size.x.div_ceil(WORD_BITS);
+        BitGrid {
+            size,
+            words_per_row,
+            data: vec![0; words_per_row * size.y],
+        }
+    }
+
+    pub fn size(&self) -> Vec2 {
+        self.size
+    }
+
+    pub fn width(&self) -> usize {
+        self.size.x
+    }
+
+    pub fn height(&self) -> usize {
+        self.size.y
+    }
+
+    /// The mask of the bits of the last word of a row that are within the
+    /// grid.
+    fn last_word_mask(&self) -> u64 {
+        match self.size.x % WORD_BITS {
+            0 => !0,
+            bits => (1 << bits) - 1,
+        }
+    }
+
+    #[inline]
+    pub fn get<V: Into<Vec2>>(&self, index: V) -> Option<bool> {
+        let index = index.into();
+        if index.x < self.size.x && index.y < self.size.y {
+            let word = self.data[index.y * self.words_per_row + index.x / WORD_BITS];
+            Some(word >> (index.x % WORD_BITS) & 1 != 0)
+        } else {
+            None
+        }
+    }
+
+    #[inline]
+    pub fn set<V: Into<Vec2>>(&mut self, index: V, value: bool) {
+        let index = index.into();
+        assert!(index.x < self.size.x);
+        assert!(index.y < self.size.y);
+        let word = &mut self.data[index.y * self.words_per_row + index.x / WORD_BITS];
+        let bit = 1 << (index.x % WORD_BITS);
+        match value {
+            true => *word |= bit,
+            false => *word &= !bit,
+        }
+    }
+
+    /// The words of row `y`.
+    pub fn row(&self, y: usize) -> &[u64] {
+        assert!(y < self.size.y);
+        &self.data[y * self.words_per_row..(y + 1) * self.words_per_row]
+    }
+
+    /// The words of row `y`, the bits beyond the width of the grid must be
+    /// left zero.
+    pub fn row_mut(&mut self, y: usize) -> &mut [u64] {
+        assert!(y < self.size.y);
+        &mut self.data[y * self.words_per_row..(y + 1) * self.words_per_row]
+    }
+
+    /// The number of cells that are set.
+    pub fn count_ones(&self) -> usize {
+        self.data
+            .iter()
+            .map(|word| word.count_ones() as usize)
+            .sum()
+    }
+
+    /// Writes row `y` of the grid shifted one cell in the direction of
+    /// `offset` into `output`, without shifting the rest of the grid. Cells
+    /// shifted in from outside of the grid are not set.
+    pub fn shifted_row(&self, y: usize, offset: Offset, output: &mut [u64]) {
+        assert_eq!(self.words_per_row, output.len());
+        let delta = crate::vecs::Vec2::<i32>::zero().neighbor(offset).unwrap();
+        let Some(source) = y
+            .checked_add_signed(-delta.y as isize)
+            .filter(|&y| y < self.size.y)
+            .map(|y| self.row(y))
+        else {
+            output.fill(0);
+            return;
+        };
+        match delta.x {
+            0 => output.copy_from_slice(source),
+            1 => {
+                let mut carry = 0;
+                for (output, &word) in output.iter_mut().zip(source) {
+                    *output = word << 1 | carry;
+                    carry = word >> (WORD_BITS - 1);
+                }
+            }
+            _ => {
+                for (i, output) in output.iter_mut().enumerate() {
+                    let next = source.get(i + 1).copied().unwrap_or(0);
+                    *output = source[i] >> 1 | next << (WORD_BITS - 1);
+                }
+            }
+        }
+        if let Some(last) = output.last_mut() {
+            *last &= self.last_word_mask();
+        }
+    }
+
+    /// Returns the grid shifted one cell in the direction of `offset`, cells
+    /// shifted in from outside of the grid are not set.
+    pub fn shift(&self, offset: Offset) -> BitGrid {
+        let mut shifted = BitGrid::zeroed(self.size);
+        for y in 0..self.size.y {
+            self.shifted_row(y, offset, shifted.row_mut(y));
+        }
+        shifted
+    }
+
+    /// Returns a grid in which a cell is set when any of its neighbors in the
+    /// directions of `offsets` is set in this grid.
+    pub fn any_neighbors(&self, offsets: &[Offset]) -> BitGrid {
+        let mut neighbors = BitGrid::zeroed(self.size);
+        let mut shifted = vec![0; self.words_per_row];
+        for y in 0..self.size.y {
+            for &offset in offsets {
+                // The neighbor in the direction of `offset` ends up at the
+                // cell itself when shifted in the opposite direction.
+                self.shifted_row(y, offset.rot_180(), &mut shifted);
+                for (word, shifted) in neighbors.row_mut(y).iter_mut().zip(&shifted) {
+                    *word |= shifted;
+                }
+            }
+        }
+        neighbors
+    }
+
+    /// Iterates over the positions of the cells that are set, row by row.
+    pub fn iter_ones(&self) -> impl Iterator<Item = Vec2> + '_ {
+        self.data.iter().enumerate().flat_map(move |(i, &word)| {
+            let (y, word_x) = (i / self.words_per_row, i % self.words_per_row * WORD_BITS);
+            let mut word = word;
+            std::iter::from_fn(move || {
+                let bit = word.trailing_zeros() as usize;
+                (word != 0).then(|| {
+                    word &= word - 1;
+                    Vec2::new(word_x + bit, y)
+                })
+            })
+        })
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (Vec2, bool)> + '_ {
+        (0..self.size.y)
+            .flat_map(move |y| (0..self.size.x).map(move |x| Vec2::new(x, y)))
+            .map(|p| (p, self[p]))
+    }
+
+    pub fn stringify(&self, mut to_char: impl FnMut(bool) -> char) -> String {
+        let mut str = String::with_capacity((self.size.x + 1) * self.size.y - 1);
+        for y in 0..self.size.y {
+            if y != 0 {
+                str.push('\n')
+            }
+            for x in 0..self.size.x {
+                str.push(to_char(self[(x, y)]));
+            }
+        }
+        str
+    }
+}
+
+impl From<&VecGrid<bool>> for BitGrid {
+    fn from(grid: &VecGrid<bool>) -> Self {
+        BitGrid::new(grid.size(), |p| grid[p])
+    }
+}
+
+impl<V: Into<Vec2>> Index<V> for BitGrid {
+    type Output = bool;
+
+    #[inline]
+    fn index(&self, index: V) -> &Self::Output {
+        let index = index.into();
+        assert!(index.x < self.size.x);
+        assert!(index.y < self.size.y);
+        match self.get(index) {
+            Some(true) => &true,
+            _ => &false,
+        }
+    }
+}
+
+macro_rules! impl_bit_op {
+    ($trait_name:ident, $trait_fn:ident, $assign_trait:ident, $assign_fn:ident) => {
+        impl $assign_trait<&BitGrid> for BitGrid {
+            fn $assign_fn(&mut self, other: &BitGrid) {
+                assert_eq!(self.size, other.size);
+                for (word, other) in self.data.iter_mut().zip(&other.data) {
+                    word.$assign_fn(other);
+                }
+            }
+        }
+
+        impl $trait_name for &BitGrid {
+            type Output = BitGrid;
+
+            fn $trait_fn(self, other: &BitGrid) -> BitGrid {
+                let mut result = self.clone();
+                result.$assign_fn(other);
+                result
+            }
+        }
+    };
+}
+
+impl_bit_op!(BitAnd, bitand, BitAndAssign, bitand_assign);
+impl_bit_op!(BitOr, bitor, BitOrAssign, bitor_assign);
+impl_bit_op!(BitXor, bitxor, BitXorAssign, bitxor_assign);
+
+impl Not for &BitGrid {
+    type Output = BitGrid;
+
+    fn not(self) -> BitGrid {
+        let mut result = self.clone();
+        let mask = self.last_word_mask();
+        for row in result.data.chunks_mut(self.words_per_row) {
+            row.iter_mut().for_each(|word| *word = !*word);
+            *row.last_mut().unwrap() &= mask;
+        }
+        result
+    }
+}
+
+/// Builds a `BitGrid`, by way of a `VecGrid<bool>`.
+#[derive(Debug, Clone)]
+pub struct BitGridBuilder(VecGridBuilder<bool>);
+
+impl GridBuilder<bool> for BitGridBuilder {
+    type Output = BitGrid;
+
+    fn new() -> Self {
+        BitGridBuilder(VecGridBuilder::new())
+    }
+
+    fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    fn push_cell(&mut self, cell: bool) -> Result<(), ParseError> {
+        self.0.push_cell(cell)
+    }
+
+    fn advance_next_line(&mut self) -> Result<(), ParseError> {
+        self.0.advance_next_line()
+    }
+
+    fn finish(self) -> Result<Self::Output, ParseError> {
+        self.0.finish().map(|grid| BitGrid::from(&grid))
+    }
+}
+
+impl RaggedGridBuilder<bool> for BitGridBuilder {
+    fn ragged(padding: bool) -> Self {
+        BitGridBuilder(VecGridBuilder::ragged(padding))
+    }
+
+    fn finish_ragged(self) -> Result<(Self::Output, Vec<Range<usize>>), ParseError> {
+        let (grid, extents) = self.0.finish_ragged()?;
+        Ok((BitGrid::from(&grid), extents))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::parsers::*;
+
+    fn parse(input: &[u8]) -> BitGrid {
+        let cell = token((b'.', false)).or(token((b'#', true)));
+        grid(cell, token(b'\n')).parse(input).unwrap().0
+    }
+
+    fn stringify(grid: &BitGrid) -> String {
+        grid.stringify(|c| if c { '#' } else { '.' })
+    }
+
+    #[test]
+    fn shifts() {
+        let grid = parse(b"#..#\n.##.\n....");
+        assert_eq!(4, grid.count_ones());
+        assert_eq!(Some(true), grid.get((2, 1)));
+        assert_eq!(".#..\n..##\n....", stringify(&grid.shift(Offset::X_POS)));
+        assert_eq!("..#.\n##..\n....", stringify(&grid.shift(Offset::X_NEG)));
+        assert_eq!("....\n#..#\n.##.", stringify(&grid.shift(Offset::Y_POS)));
+        assert_eq!(".##.\n....\n....", stringify(&grid.shift(Offset::Y_NEG)));
+        assert_eq!(
+            vec![
+                Vec2::new(0, 0),
+                Vec2::new(3, 0),
+                Vec2::new(1, 1),
+                Vec2::new(2, 1)
+            ],
+            grid.iter_ones().collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn wide_rows() {
+        let mut grid = BitGrid::new((130, 2), |p| p.x == 63 || p.x == 129);
+        assert_eq!(
+            vec![Vec2::new(64, 0), Vec2::new(64, 1)],
+            grid.shift(Offset::X_POS).iter_ones().collect::<Vec<_>>()
+        );
+        assert_eq!(
+            vec![Vec2::new(62, 1), Vec2::new(128, 1)],
+            grid.shift(Offset::X_NEG_Y_POS)
+                .iter_ones()
+                .collect::<Vec<_>>()
+        );
+        assert_eq!(256, (!&grid).count_ones());
+        grid.set((63, 1), false);
+        assert_eq!(3, grid.count_ones());
+    }
+
+    #[test]
+    fn neighbors() {
+        let grid = parse(b"#....\n.....\n..#.#");
+        let neighbors = grid.any_neighbors(&Offset::ALL);
+        assert_eq!(".#...\n#####\n.#.#.", stringify(&neighbors));
+        let isolated = &grid & &!&neighbors;
+        assert_eq!(grid, isolated);
+        assert_eq!(0, (&grid ^ &grid).count_ones());
+        assert_eq!(9, (&grid | &neighbors.shift(Offset::Y_NEG)).count_ones());
+    }
+}
@@ -0,0 +1,323 @@
+use super::{
+    search::{self, SearchGrid},
+    GridView, GridViewMut, VecGrid,
+};
+use std::ops::{Index, IndexMut};
+
+type Vec2 = crate::vecs::Vec2<usize>;
+type Vec3 = crate::vecs::Vec3<usize>;
+type Delta = crate::vecs::Vec3<isize>;
+
+/// The offsets to the six cells that share a face with a cell.
+pub const ORTHOGONAL_3D: [Delta; 6] = [
+    Delta::new(1, 0, 0),
+    Delta::new(-1, 0, 0),
+    Delta::new(0, 1, 0),
+    Delta::new(0, -1, 0),
+    Delta::new(0, 0, 1),
+    Delta::new(0, 0, -1),
+];
+
+/// A 3D grid, made up of layers along the Z axis that are each a 2D grid.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VecGrid3<T> {
+    size: Vec3,
+    data: Vec<T>,
+}
+
+impl<T> VecGrid3<T> {
+    pub fn new(size: impl Into<Vec3>, mut initializer: impl FnMut(Vec3) -> T) -> VecGrid3<T> {
+        let size = size.into();
+        let mut data = Vec::with_capacity(size.x * size.y * size.z);
+        for z in 0..size.z {
+            for y in 0..size.y {
+                for x in 0..size.x {
+                    data.push(initializer(Vec3::new(x, y, z)));
+                }
+            }
+        }
+        VecGrid3 { size, data }
+    }
+
+    /// Stacks 2D grids along the Z axis, the first grid becomes layer zero.
+    ///
+    /// # Panics
+    /// When the layers are not all the same size.
+    pub fn from_layers(layers: impl IntoIterator<Item = VecGrid<T>>) -> VecGrid3<T> {
+        let mut size = None;
+        let mut depth = 0;
+        let mut data = Vec::new();
+        for layer in layers {
+            let layer_size = *size.get_or_insert(layer.size);
+            assert_eq!(layer_size, layer.size, "layers differ in size");
+            data.extend(layer.data);
+            depth += 1;
+        }
+        let Vec2 { x, y } = size.unwrap_or(Vec2::zero());
+        VecGrid3 {
+            size: Vec3::new(x, y, depth),
+            data,
+        }
+    }
+
+    pub fn size(&self) -> Vec3 {
+        self.size
+    }
+
+    pub fn width(&self) -> usize {
+        self.size.x
+    }
+
+    pub fn height(&self) -> usize {
+        self.size.y
+    }
+
+    pub fn depth(&self) -> usize {
+        self.size.z
+    }
+
+    #[inline]
+    fn index_of(&self, index: Vec3) -> Option<usize> {
+        let Vec3 { x, y, z } = index;
+        (x < self.size.x && y < self.size.y && z < self.size.z)
+            .then(|| (z * self.size.y + y) * self.size.x + x)
+    }
+
+    #[inline]
+    pub fn get<V: Into<Vec3>>(&self, index: V) -> Option<&T> {
+        let index = self.index_of(index.into())?;
+        Some(&self.data[index])
+    }
+
+    #[inline]
+    pub fn get_mut<V: Into<Vec3>>(&mut self, index: V) -> Option<&mut T> {
+        let index = self.index_of(index.into())?;
+        Some(&mut self.data[index])
+    }
+
+    /// The position one of `offset` away from `position`, if it is in the grid.
+    pub fn neighbor(&self, position: Vec3, offset: Delta) -> Option<Vec3> {
+        let neighbor = Vec3::new(
+            position.x.checked_add_signed(offset.x)?,
+            position.y.checked_add_signed(offset.y)?,
+            position.z.checked_add_signed(offset.z)?,
+        );
+        self.index_of(neighbor).map(|_| neighbor)
+    }
+
+    /// Returns the cells, layer by layer.
+    #[inline]
+    pub fn cells(&self) -> &[T] {
+        &self.data
+    }
+
+    #[inline]
+    pub fn cells_mut(&mut self) -> &mut [T] {
+        &mut self.data
+    }
+
+    fn position_of(size: Vec3, index: usize) -> Vec3 {
+        let (x, y) = (index % size.x, index / size.x);
+        Vec3::new(x, y % size.y, y / size.y)
+    }
+
+    /// Iterates over the cells, layer by layer and row by row.
+    pub fn iter(&self) -> impl DoubleEndedIterator<Item = (Vec3, &T)> + ExactSizeIterator {
+        let size = self.size;
+        (self.data.iter())
+            .enumerate()
+            .map(move |(i, cell)| (Self::position_of(size, i), cell))
+    }
+
+    pub fn iter_mut(
+        &mut self,
+    ) -> impl DoubleEndedIterator<Item = (Vec3, &mut T)> + ExactSizeIterator {
+        let size = self.size;
+        (self.data.iter_mut())
+            .enumerate()
+            .map(move |(i, cell)| (Self::position_of(size, i), cell))
+    }
+
+    /// Borrows the plane at `z` as a 2D grid.
+    pub fn layer(&self, z: usize) -> GridView<'_, T> {
+        assert!(z < self.size.z);
+        let area = self.size.x * self.size.y;
+        GridView {
+            data: &self.data[z * area..(z + 1) * area],
+            stride: self.size.x,
+            size: Vec2::new(self.size.x, self.size.y),
+        }
+    }
+
+    pub fn layer_mut(&mut self, z: usize) -> GridViewMut<'_, T> {
+        assert!(z < self.size.z);
+        let area = self.size.x * self.size.y;
+        GridViewMut {
+            data: &mut self.data[z * area..(z + 1) * area],
+            stride: self.size.x,
+            size: Vec2::new(self.size.x, self.size.y),
+        }
+    }
+
+    pub fn layers(&self) -> impl DoubleEndedIterator<Item = GridView<'_, T>> + ExactSizeIterator {
+        (0..self.size.z).map(|z| self.layer(z))
+    }
+
+    /// Copies the plane at `x` into a 2D grid, indexed by `(y, z)`.
+    pub fn slice_x(&self, x: usize) -> VecGrid<T>
+    where
+        T: Clone,
+    {
+        assert!(x < self.size.x);
+        VecGrid::new((self.size.y, self.size.z), |p| self[(x, p.x, p.y)].clone())
+    }
+
+    /// Copies the plane at `y` into a 2D grid, indexed by `(x, z)`.
+    pub fn slice_y(&self, y: usize) -> VecGrid<T>
+    where
+        T: Clone,
+    {
+        assert!(y < self.size.y);
+        VecGrid::new((self.size.x, self.size.z), |p| self[(p.x, y, p.y)].clone())
+    }
+
+    /// Returns the cells that can be reached from `start` by moving along
+    /// `offsets`, such as `ORTHOGONAL_3D`, only through cells that are
+    /// `passable`, see `VecGrid::flood_fill`.
+    pub fn flood_fill(
+        &self,
+        start: impl Into<Vec3>,
+        offsets: &[Delta],
+        passable: impl FnMut(&T) -> bool,
+    ) -> VecGrid3<bool> {
+        search::flood_fill(self, start.into(), offsets, passable)
+    }
+
+    /// Labels the groups of `passable` cells that are connected by moving along
+    /// `offsets`, see `VecGrid::components`.
+    pub fn components(
+        &self,
+        offsets: &[Delta],
+        passable: impl FnMut(&T) -> bool,
+    ) -> (VecGrid3<Option<u32>>, u32) {
+        search::components(self, offsets, passable)
+    }
+
+    /// Returns the fewest number of moves along `offsets` that are needed to
+    /// reach each cell from the closest of `sources`, see `VecGrid::distances`.
+    pub fn distances(
+        &self,
+        sources: impl IntoIterator<Item = impl Into<Vec3>>,
+        offsets: &[Delta],
+        can_move: impl FnMut(&T, &T) -> bool,
+    ) -> VecGrid3<Option<u32>> {
+        let sources = sources.into_iter().map(Into::into);
+        search::distances(self, sources, offsets, can_move)
+    }
+
+    /// Formats the layers like `VecGrid::stringify`, separated by empty lines.
+    pub fn stringify(&self, mut to_char: impl FnMut(&T) -> char) -> String {
+        let layers = self.layers().map(|layer| layer.stringify(&mut to_char));
+        layers.collect::<Vec<_>>().join("\n\n")
+    }
+}
+
+impl<T, V: Into<Vec3>> Index<V> for VecGrid3<T> {
+    type Output = T;
+
+    #[inline]
+    fn index(&self, index: V) -> &Self::Output {
+        let index = index.into();
+        let index = self
+            .index_of(index)
+            .unwrap_or_else(|| panic!("{index:?} is outside of a grid of {:?}", self.size));
+        &self.data[index]
+    }
+}
+
+impl<T, V: Into<Vec3>> IndexMut<V> for VecGrid3<T> {
+    #[inline]
+    fn index_mut(&mut self, index: V) -> &mut Self::Output {
+        let index = index.into();
+        let size = self.size;
+        let index = self
+            .index_of(index)
+            .unwrap_or_else(|| panic!("{index:?} is outside of a grid of {size:?}"));
+        &mut self.data[index]
+    }
+}
+
+impl<T> SearchGrid<T> for VecGrid3<T> {
+    type Position = Vec3;
+    type Offset = Delta;
+    type Map<U> = VecGrid3<U>;
+
+    fn map_of<U: Clone>(&self, value: U) -> VecGrid3<U> {
+        VecGrid3::new(self.size, |_| value.clone())
+    }
+
+    fn cell_count(&self) -> usize {
+        self.data.len()
+    }
+
+    fn position_of(&self, index: usize) -> Vec3 {
+        Self::position_of(self.size, index)
+    }
+
+    fn contains(&self, position: Vec3) -> bool {
+        self.index_of(position).is_some()
+    }
+
+    fn neighbor(&self, position: Vec3, offset: Delta) -> Option<Vec3> {
+        VecGrid3::neighbor(self, position, offset)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn grid3() {
+        let grid = VecGrid3::new((3, 2, 2), |p| p.z * 6 + p.y * 3 + p.x);
+        assert_eq!((0..12).collect::<Vec<_>>(), grid.cells());
+        assert_eq!(Some(&10), grid.get((1, 1, 1)));
+        assert_eq!(None, grid.get((1, 2, 1)));
+        assert_eq!((Vec3::new(2, 0, 1), &8), grid.iter().nth(8).unwrap());
+        assert_eq!(vec![9, 10, 11], grid.layer(1).row(1));
+        assert_eq!(
+            VecGrid::new((2, 2), |p| p.y * 6 + p.x * 3 + 1),
+            grid.slice_x(1)
+        );
+        assert_eq!(VecGrid::new((3, 2), |p| p.y * 6 + 3 + p.x), grid.slice_y(1));
+        let layers = grid.layers().map(|layer| layer.to_grid());
+        assert_eq!(grid, VecGrid3::from_layers(layers));
+        assert_eq!(None, grid.neighbor(Vec3::new(0, 0, 1), Delta::new(0, 0, 1)));
+        assert_eq!(
+            Some(Vec3::new(0, 0, 0)),
+            grid.neighbor(Vec3::new(0, 0, 1), Delta::new(0, 0, -1))
+        );
+    }
+
+    #[test]
+    fn search() {
+        // A hollow 3x3x3 cube, with an extra layer of space around it.
+        let cube = VecGrid3::new((5, 5, 5), |p| {
+            let inside = |c: usize| (1..=3).contains(&c);
+            inside(p.x) && inside(p.y) && inside(p.z) && p != Vec3::from(2)
+        });
+        let outside = cube.flood_fill((0, 0, 0), &ORTHOGONAL_3D, |&solid| !solid);
+        assert_eq!(125 - 27, outside.cells().iter().filter(|&&b| b).count());
+        assert!(!outside[(2, 2, 2)]);
+        let (labels, count) = cube.components(&ORTHOGONAL_3D, |&solid| !solid);
+        assert_eq!(2, count);
+        assert_eq!(Some(1), labels[(2, 2, 2)]);
+        assert_eq!(Some(0), labels[(4, 4, 4)]);
+        let distances = cube.distances([(0, 0, 0)], &ORTHOGONAL_3D, |_, &solid| !solid);
+        assert_eq!(Some(12), distances[(4, 4, 4)]);
+        assert_eq!(Some(8), distances[(2, 2, 4)]);
+        assert_eq!(None, distances[(2, 2, 2)]);
+        let distances = cube.distances([(2, 2, 2), (0, 0, 0)], &ORTHOGONAL_3D, |_, _| true);
+        assert_eq!(Some(2), distances[(2, 2, 4)]);
+    }
+}
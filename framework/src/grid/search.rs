@@ -0,0 +1,236 @@
+use super::VecGrid;
+use crate::offsets::{Neighbor, Offset};
+use std::collections::VecDeque;
+use std::ops::{Index, IndexMut};
+
+type Vec2 = crate::vecs::Vec2<usize>;
+
+/// What the searches need from a grid, so that they work the same in 2D and
+/// 3D: the neighbors of a position, and a grid of the same size to record
+/// something about each position in.
+pub(super) trait SearchGrid<T>: Index<Self::Position, Output = T> {
+    type Position: Copy;
+    type Offset: Copy;
+    /// A grid of the same size, holding a `U` for each position.
+    type Map<U>: Index<Self::Position, Output = U> + IndexMut<Self::Position>;
+
+    /// Creates a grid of the same size, with all cells set to `value`.
+    fn map_of<U: Clone>(&self, value: U) -> Self::Map<U>;
+    /// The number of cells in the grid.
+    fn cell_count(&self) -> usize;
+    /// The position of the cell at `index`, in the order in which the cells
+    /// are stored.
+    fn position_of(&self, index: usize) -> Self::Position;
+    fn contains(&self, position: Self::Position) -> bool;
+    /// The position one of `offset` away from `position`, if it is in the grid.
+    fn neighbor(&self, position: Self::Position, offset: Self::Offset) -> Option<Self::Position>;
+}
+
+impl<T> SearchGrid<T> for VecGrid<T> {
+    type Position = Vec2;
+    type Offset = Offset;
+    type Map<U> = VecGrid<U>;
+
+    fn map_of<U: Clone>(&self, value: U) -> VecGrid<U> {
+        VecGrid::new(self.size(), |_| value.clone())
+    }
+
+    fn cell_count(&self) -> usize {
+        self.cells().len()
+    }
+
+    fn position_of(&self, index: usize) -> Vec2 {
+        Vec2::new(index % self.width(), index / self.width())
+    }
+
+    fn contains(&self, position: Vec2) -> bool {
+        self.get(position).is_some()
+    }
+
+    fn neighbor(&self, position: Vec2, offset: Offset) -> Option<Vec2> {
+        position.neighbor(offset).filter(|&p| self.contains(p))
+    }
+}
+
+/// Returns the cells that can be reached from `start` by moving along
+/// `offsets`, only through cells that are `passable`.
+pub(super) fn flood_fill<G: SearchGrid<T>, T>(
+    grid: &G,
+    start: G::Position,
+    offsets: &[G::Offset],
+    mut passable: impl FnMut(&T) -> bool,
+) -> G::Map<bool> {
+    let mut filled = grid.map_of(false);
+    if !grid.contains(start) || !passable(&grid[start]) {
+        return filled;
+    }
+    filled[start] = true;
+    let mut stack = vec![start];
+    while let Some(position) = stack.pop() {
+        for neighbor in offsets.iter().filter_map(|&o| grid.neighbor(position, o)) {
+            if !filled[neighbor] && passable(&grid[neighbor]) {
+                filled[neighbor] = true;
+                stack.push(neighbor);
+            }
+        }
+    }
+    filled
+}
+
+/// Labels the groups of `passable` cells that are connected by moving along
+/// `offsets`, with labels starting at zero in storage order.
+pub(super) fn components<G: SearchGrid<T>, T>(
+    grid: &G,
+    offsets: &[G::Offset],
+    mut passable: impl FnMut(&T) -> bool,
+) -> (G::Map<Option<u32>>, u32) {
+    let mut labels = grid.map_of(None);
+    let mut count = 0;
+    let mut stack = Vec::new();
+    for index in 0..grid.cell_count() {
+        let start = grid.position_of(index);
+        if labels[start].is_some() || !passable(&grid[start]) {
+            continue;
+        }
+        labels[start] = Some(count);
+        stack.push(start);
+        while let Some(position) = stack.pop() {
+            for neighbor in offsets.iter().filter_map(|&o| grid.neighbor(position, o)) {
+                if labels[neighbor].is_none() && passable(&grid[neighbor]) {
+                    labels[neighbor] = Some(count);
+                    stack.push(neighbor);
+                }
+            }
+        }
+        count += 1;
+    }
+    (labels, count)
+}
+
+/// Returns the fewest number of moves along `offsets` from the closest of
+/// `sources` to each cell, for moves that `can_move` allows.
+pub(super) fn distances<G: SearchGrid<T>, T>(
+    grid: &G,
+    sources: impl IntoIterator<Item = G::Position>,
+    offsets: &[G::Offset],
+    mut can_move: impl FnMut(&T, &T) -> bool,
+) -> G::Map<Option<u32>> {
+    let mut distances = grid.map_of(None);
+    let mut queue = VecDeque::new();
+    for source in sources {
+        if grid.contains(source) && distances[source].is_none() {
+            distances[source] = Some(0);
+            queue.push_back(source);
+        }
+    }
+    while let Some(position) = queue.pop_front() {
+        let cell = &grid[position];
+        let distance = distances[position].map(|d| d + 1);
+        for neighbor in offsets.iter().filter_map(|&o| grid.neighbor(position, o)) {
+            if distances[neighbor].is_none() && can_move(cell, &grid[neighbor]) {
+                distances[neighbor] = distance;
+                queue.push_back(neighbor);
+            }
+        }
+    }
+    distances
+}
+
+impl<T> VecGrid<T> {
+    /// Returns the cells that can be reached from `start` by moving along
+    /// `offsets`, such as `Offset::ORTHOGONAL` or `Offset::ALL`, only through
+    /// cells that are `passable`. Nothing is reached if `start` isn't passable.
+    /// Use `BitGrid::from` for a compact copy of the result.
+    pub fn flood_fill(
+        &self,
+        start: impl Into<Vec2>,
+        offsets: &[Offset],
+        passable: impl FnMut(&T) -> bool,
+    ) -> VecGrid<bool> {
+        flood_fill(self, start.into(), offsets, passable)
+    }
+
+    /// Labels the groups of `passable` cells that are connected by moving along
+    /// `offsets`, with labels starting at zero in reading order. Returns the
+    /// labels, which are `None` for cells that aren't passable, and the number
+    /// of groups.
+    pub fn components(
+        &self,
+        offsets: &[Offset],
+        passable: impl FnMut(&T) -> bool,
+    ) -> (VecGrid<Option<u32>>, u32) {
+        components(self, offsets, passable)
+    }
+
+    /// Returns the fewest number of moves along `offsets` that are needed to
+    /// reach each cell from the closest of `sources`, or `None` if it can't be
+    /// reached. Moving from one cell to the next is only possible if `can_move`
+    /// returns `true` for those cells, use `|_, to| passable(to)` to only check
+    /// the cell that is moved to.
+    pub fn distances(
+        &self,
+        sources: impl IntoIterator<Item = impl Into<Vec2>>,
+        offsets: &[Offset],
+        can_move: impl FnMut(&T, &T) -> bool,
+    ) -> VecGrid<Option<u32>> {
+        distances(self, sources.into_iter().map(Into::into), offsets, can_move)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::parsers::*;
+
+    fn parse(input: &[u8]) -> VecGrid<bool> {
+        grid(token((b'#', true)).or(token((b'.', false))), token(b'\n'))
+            .execute(input)
+            .unwrap()
+    }
+
+    fn count(filled: &VecGrid<bool>) -> usize {
+        filled.cells().iter().filter(|&&b| b).count()
+    }
+
+    #[test]
+    fn flood_fill() {
+        let walls = parse(b"..#..\n.#...\n#..#.\n...#.");
+        let filled = walls.flood_fill((1, 2), &Offset::ORTHOGONAL, |&wall| !wall);
+        assert_eq!(12, count(&filled));
+        assert_eq!(
+            "...oo\n..ooo\n.oo.o\nooo.o",
+            filled.stringify(|&b| if b { 'o' } else { '.' })
+        );
+        let filled = walls.flood_fill((1, 2), &Offset::ALL, |&wall| !wall);
+        assert_eq!(15, count(&filled));
+        let filled = walls.flood_fill((2, 0), &Offset::ALL, |&wall| !wall);
+        assert_eq!(0, count(&filled));
+    }
+
+    #[test]
+    fn components() {
+        let walls = parse(b"..#..\n.#...\n#..#.\n...#.");
+        let (labels, count) = walls.components(&Offset::ORTHOGONAL, |&wall| !wall);
+        assert_eq!(2, count);
+        let to_char = |label: &Option<u32>| label.map_or('#', |l| (b'a' + l as u8) as char);
+        assert_eq!("aa#bb\na#bbb\n#bb#b\nbbb#b", labels.stringify(to_char));
+        let (labels, count) = walls.components(&Offset::ALL, |&wall| !wall);
+        assert_eq!(1, count);
+        assert_eq!(Some(0), labels[(0, 0)]);
+        assert_eq!(0, walls.components(&Offset::ALL, |_| false).1);
+    }
+
+    #[test]
+    fn distances() {
+        let walls = parse(b"..#..\n.#...\n#..#.\n...#.");
+        let distances = walls.distances([(0, 0), (4, 3)], &Offset::ORTHOGONAL, |_, &wall| !wall);
+        let to_char = |d: &Option<u32>| d.map_or('#', |d| char::from_digit(d, 36).unwrap());
+        assert_eq!("01#43\n1#432\n#65#1\n876#0", distances.stringify(to_char));
+        // Only moving downhill.
+        let heights = VecGrid::new((3, 2), |p| p.x + p.y);
+        let distances = heights.distances([(2, 1)], &Offset::ORTHOGONAL, |from, to| to < from);
+        assert_eq!("321\n210", distances.stringify(to_char));
+        let distances = heights.distances([(0, 0)], &Offset::ORTHOGONAL, |from, to| to < from);
+        assert_eq!("0##\n###", distances.stringify(to_char));
+    }
+}
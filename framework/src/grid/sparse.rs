@@ -0,0 +1,352 @@
+use super::{Grid, GridBuilder, RaggedGridBuilder};
+use crate::parsers::ParseError;
+use std::ops::{Index, IndexMut, Range};
+
+type Vec2 = crate::vecs::Vec2<i32>;
+
+/// The number of bits of a coordinate that select a cell within a chunk.
+const CHUNK_BITS: u32 = 4;
+const CHUNK_SIZE: i32 = 1 << CHUNK_BITS;
+
+/// Splits a position into the position of its chunk, and the index of the
+/// cell within that chunk.
+#[inline]
+fn split(position: Vec2) -> (Vec2, usize) {
+    let chunk = Vec2::new(position.x >> CHUNK_BITS, position.y >> CHUNK_BITS);
+    let mask = CHUNK_SIZE - 1;
+    let index = (position.y & mask) * CHUNK_SIZE + (position.x & mask);
+    (chunk, index as usize)
+}
+
+/// A grid without fixed bounds, which grows in any direction as cells are
+/// written, including towards negative coordinates. Cells are allocated in
+/// square chunks, cells that have never been written read as the default cell.
+#[derive(Debug, Clone)]
+pub struct SparseGrid<T> {
+    /// A table of `chunks_size` chunks, row by row, the first of which is the
+    /// chunk at `chunks_origin`. Chunks that have never been written are
+    /// `None`.
+    chunks: Vec<Option<Box<[T]>>>,
+    chunks_origin: Vec2,
+    chunks_size: Vec2,
+    default: T,
+    /// The smallest and largest position that has been written, inclusive.
+    bounds: Option<(Vec2, Vec2)>,
+}
+
+impl<T: Clone + Default> Grid<T> for SparseGrid<T> {
+    type Indexer = Vec2;
+    type Builder = SparseGridBuilder<T>;
+}
+
+impl<T> SparseGrid<T> {
+    pub fn new(default: T) -> SparseGrid<T> {
+        SparseGrid {
+            chunks: Vec::new(),
+            chunks_origin: Vec2::zero(),
+            chunks_size: Vec2::zero(),
+            default,
+            bounds: None,
+        }
+    }
+
+    /// The value of the cells that have never been written.
+    pub fn default_cell(&self) -> &T {
+        &self.default
+    }
+
+    /// The smallest and largest position that has been written, inclusive,
+    /// or `None` if no cell has been written yet. Borrowing a cell mutably
+    /// counts as writing it.
+    pub fn bounds(&self) -> Option<(Vec2, Vec2)> {
+        self.bounds
+    }
+
+    /// The size of the bounding box, zero if no cell has been written yet.
+    pub fn size(&self) -> Vec2 {
+        match self.bounds {
+            Some((min, max)) => max + 1 - min,
+            None => Vec2::zero(),
+        }
+    }
+
+    fn in_bounds(&self, index: Vec2) -> bool {
+        self.bounds.is_some_and(|(min, max)| {
+            (min.x..=max.x).contains(&index.x) && (min.y..=max.y).contains(&index.y)
+        })
+    }
+
+    /// Returns the index of a chunk in the table, if the table covers it.
+    #[inline]
+    fn chunk_index(&self, chunk: Vec2) -> Option<usize> {
+        let relative = chunk - self.chunks_origin;
+        let in_table = (relative.x as u32) < self.chunks_size.x as u32
+            && (relative.y as u32) < self.chunks_size.y as u32;
+        in_table.then(|| (relative.y * self.chunks_size.x + relative.x) as usize)
+    }
+
+    /// Grows the table of chunks to cover `chunk`. In each direction in
+    /// which it grows, it grows by at least its current size, so that the
+    /// cost of moving the chunks is amortized.
+    fn grow(&mut self, chunk: Vec2) {
+        let (min, max) = if self.chunks.is_empty() {
+            (chunk, chunk)
+        } else {
+            let old_max = self.chunks_origin + self.chunks_size - 1;
+            let grow_min = |old: i32, new: i32, size: i32| match new < old {
+                true => new.min(old - size),
+                false => old,
+            };
+            let grow_max = |old: i32, new: i32, size: i32| match new > old {
+                true => new.max(old + size),
+                false => old,
+            };
+            (
+                Vec2::new(
+                    grow_min(self.chunks_origin.x, chunk.x, self.chunks_size.x),
+                    grow_min(self.chunks_origin.y, chunk.y, self.chunks_size.y),
+                ),
+                Vec2::new(
+                    grow_max(old_max.x, chunk.x, self.chunks_size.x),
+                    grow_max(old_max.y, chunk.y, self.chunks_size.y),
+                ),
+            )
+        };
+        let size = max + 1 - min;
+        let mut chunks = Vec::with_capacity((size.x * size.y) as usize);
+        chunks.resize_with((size.x * size.y) as usize, || None);
+        let old_chunks = std::mem::take(&mut self.chunks);
+        for (index, cells) in old_chunks.into_iter().enumerate() {
+            let index = index as i32;
+            let position = self.chunks_origin
+                + Vec2::new(index % self.chunks_size.x, index / self.chunks_size.x)
+                - min;
+            chunks[(position.y * size.x + position.x) as usize] = cells;
+        }
+        self.chunks = chunks;
+        self.chunks_origin = min;
+        self.chunks_size = size;
+    }
+
+    /// Returns `None` for positions outside of the bounding box.
+    #[inline]
+    pub fn get<V: Into<Vec2>>(&self, index: V) -> Option<&T> {
+        let index = index.into();
+        self.in_bounds(index).then(|| &self[index])
+    }
+
+    /// Returns `None` for positions outside of the bounding box, use
+    /// `IndexMut` to grow the grid.
+    #[inline]
+    pub fn get_mut<V: Into<Vec2>>(&mut self, index: V) -> Option<&mut T>
+    where
+        T: Clone,
+    {
+        let index = index.into();
+        self.in_bounds(index).then(|| &mut self[index])
+    }
+
+    /// Iterates over all cells in the bounding box, row by row.
+    pub fn iter(&self) -> impl Iterator<Item = (Vec2, &T)> {
+        let (min, max) = self.bounds.unwrap_or((Vec2::zero(), Vec2::new(-1, -1)));
+        (min.y..=max.y)
+            .flat_map(move |y| (min.x..=max.x).map(move |x| Vec2::new(x, y)))
+            .map(|p| (p, &self[p]))
+    }
+
+    /// Formats the cells in the bounding box, row by row.
+    pub fn stringify(&self, mut to_char: impl FnMut(&T) -> char) -> String {
+        let mut str = String::new();
+        let Some((min, max)) = self.bounds else {
+            return str;
+        };
+        for y in min.y..=max.y {
+            if y != min.y {
+                str.push('\n');
+            }
+            for x in min.x..=max.x {
+                str.push(to_char(&self[(x, y)]));
+            }
+        }
+        str
+    }
+}
+
+impl<T, V: Into<Vec2>> Index<V> for SparseGrid<T> {
+    type Output = T;
+
+    #[inline]
+    fn index(&self, index: V) -> &Self::Output {
+        let (chunk, index) = split(index.into());
+        match self
+            .chunk_index(chunk)
+            .and_then(|c| self.chunks[c].as_deref())
+        {
+            Some(cells) => &cells[index],
+            None => &self.default,
+        }
+    }
+}
+
+impl<T: Clone, V: Into<Vec2>> IndexMut<V> for SparseGrid<T> {
+    #[inline]
+    fn index_mut(&mut self, index: V) -> &mut Self::Output {
+        let position = index.into();
+        self.bounds = Some(match self.bounds {
+            Some((min, max)) => (min.min_comp(position), max.max_comp(position)),
+            None => (position, position),
+        });
+        let (chunk, index) = split(position);
+        let chunk_index = match self.chunk_index(chunk) {
+            Some(chunk_index) => chunk_index,
+            None => {
+                self.grow(chunk);
+                self.chunk_index(chunk).unwrap()
+            }
+        };
+        let default = &self.default;
+        let cells = self.chunks[chunk_index].get_or_insert_with(|| {
+            vec![default.clone(); (CHUNK_SIZE * CHUNK_SIZE) as usize].into()
+        });
+        &mut cells[index]
+    }
+}
+
+/// Builds a `SparseGrid` with the first cell at the origin. Cells that are
+/// not parsed, such as the cells after a short row of a ragged grid, are
+/// default cells.
+#[derive(Debug, Clone)]
+pub struct SparseGridBuilder<T> {
+    grid: SparseGrid<T>,
+    position: Vec2,
+    width: Option<i32>,
+    /// In ragged mode, the number of cells on each completed row.
+    row_lengths: Option<Vec<usize>>,
+}
+
+impl<T: Clone + Default> GridBuilder<T> for SparseGridBuilder<T> {
+    type Output = SparseGrid<T>;
+
+    fn new() -> Self {
+        SparseGridBuilder {
+            grid: SparseGrid::new(T::default()),
+            position: Vec2::zero(),
+            width: None,
+            row_lengths: None,
+        }
+    }
+
+    fn is_empty(&self) -> bool {
+        self.grid.bounds.is_none()
+    }
+
+    fn push_cell(&mut self, cell: T) -> Result<(), ParseError> {
+        let ragged = self.row_lengths.is_some();
+        if !ragged && self.width.is_some_and(|width| self.position.x >= width) {
+            return Err(ParseError::GridCellAfterEndOfRowReached);
+        }
+        self.grid[self.position] = cell;
+        self.position.x += 1;
+        Ok(())
+    }
+
+    fn advance_next_line(&mut self) -> Result<(), ParseError> {
+        if let Some(row_lengths) = &mut self.row_lengths {
+            row_lengths.push(self.position.x as usize);
+        } else {
+            match self.width {
+                Some(width) if width != self.position.x => {
+                    return Err(ParseError::GridIncompleteRow)
+                }
+                Some(_) => {}
+                None => self.width = Some(self.position.x),
+            }
+        }
+        self.position = Vec2::new(0, self.position.y + 1);
+        Ok(())
+    }
+
+    fn finish(self) -> Result<Self::Output, ParseError> {
+        let incomplete = self
+            .width
+            .is_some_and(|width| self.position.x != 0 && self.position.x != width);
+        if self.row_lengths.is_none() && incomplete {
+            return Err(ParseError::GridIncompleteRow);
+        }
+        Ok(self.grid)
+    }
+}
+
+impl<T: Clone + Default + PartialEq> RaggedGridBuilder<T> for SparseGridBuilder<T> {
+    /// The padding becomes the default cell of the grid.
+    fn ragged(padding: T) -> Self {
+        let mut builder = Self::new();
+        builder.grid.default = padding;
+        builder.row_lengths = Some(Vec::new());
+        builder
+    }
+
+    fn finish_ragged(mut self) -> Result<(Self::Output, Vec<Range<usize>>), ParseError> {
+        let row_lengths = self.row_lengths.as_ref().expect("grid is not ragged");
+        if self.position.x != 0 || row_lengths.is_empty() {
+            self.advance_next_line()?;
+        }
+        let grid = self.grid;
+        let extents = (self.row_lengths.unwrap().into_iter().enumerate())
+            .map(|(y, length)| {
+                let start = (0..length)
+                    .position(|x| grid[(x as i32, y as i32)] != grid.default)
+                    .unwrap_or(length);
+                start..length
+            })
+            .collect();
+        Ok((grid, extents))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::offsets::{Neighbors, Offset};
+    use crate::parsers::*;
+
+    #[test]
+    fn growing() {
+        let mut grid = SparseGrid::new('.');
+        assert_eq!(None, grid.bounds());
+        assert_eq!("", grid.stringify(|&c| c));
+        grid[(0, 0)] = 'a';
+        grid[(-20, 2)] = 'b';
+        grid[(-19, -33)] = 'c';
+        assert_eq!(Some((Vec2::new(-20, -33), Vec2::new(0, 2))), grid.bounds());
+        assert_eq!(Vec2::new(21, 36), grid.size());
+        assert_eq!('.', grid[(100, 100)]);
+        assert_eq!(None, grid.get((100, 100)));
+        assert_eq!(Some(&'b'), grid.get((-20, 2)));
+        assert_eq!(3, grid.iter().filter(|&(_, &c)| c != '.').count());
+    }
+
+    #[test]
+    fn parsing() {
+        let cell = token((b'.', false)).or(token((b'#', true)));
+        let p = grid::<SparseGrid<bool>, _, _>(cell, token(b'\n'));
+        let mut elves = p.parse(b"#..\n.#.\n").unwrap().0;
+        assert_eq!("#..\n.#.", elves.stringify(|&c| if c { '#' } else { '.' }));
+        for neighbor in Vec2::zero().neighbors(&Offset::ALL) {
+            elves[neighbor] = true;
+        }
+        assert_eq!(
+            "###.\n###.\n###.",
+            elves.stringify(|&c| if c { '#' } else { '.' })
+        );
+        assert_eq!(
+            Err((ParseError::GridIncompleteRow, &b"#..\n.#"[..])),
+            p.parse(b"#..\n.#").map(|_| ())
+        );
+
+        let p = grid::<SparseGrid<u8>, _, _>(pattern!(b'a'..=b'z'), token(b'\n')).ragged(b' ');
+        let ((letters, extents), _) = p.parse(b"abc\nd\nef").unwrap();
+        assert_eq!("abc\nd  \nef ", letters.stringify(|&c| c as char));
+        assert_eq!(vec![0..3, 0..1, 0..2], extents);
+    }
+}
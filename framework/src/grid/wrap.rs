@@ -0,0 +1,268 @@
+use super::VecGrid;
+use crate::offsets::{Neighbor, Offset};
+use std::ops::Index;
+
+type Vec2 = crate::vecs::Vec2<usize>;
+type Delta = crate::vecs::Vec2<isize>;
+
+/// The step an offset makes along each axis.
+fn delta(offset: Offset) -> Delta {
+    Delta::zero().neighbor(offset).unwrap()
+}
+
+/// Describes how positions wrap around, like on a torus: positions within the
+/// region of `size` at `origin` that move out of one side of the region come
+/// back in on the other side, along the axes for which wrapping is enabled.
+/// Positions outside of the region, such as the walls around it, don't wrap.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Wrap {
+    origin: Vec2,
+    size: Vec2,
+    x: bool,
+    y: bool,
+}
+
+impl Wrap {
+    /// Wraps along both axes, within the rectangle of `size` at the origin.
+    pub fn new(size: impl Into<Vec2>) -> Wrap {
+        Wrap::region(Vec2::zero(), size)
+    }
+
+    /// Wraps along both axes, within the rectangle of `size` at `origin`.
+    pub fn region(origin: impl Into<Vec2>, size: impl Into<Vec2>) -> Wrap {
+        let size = size.into();
+        assert!(size.x > 0);
+        assert!(size.y > 0);
+        Wrap {
+            origin: origin.into(),
+            size,
+            x: true,
+            y: true,
+        }
+    }
+
+    /// Only wraps along the X axis.
+    pub fn x_only(self) -> Wrap {
+        Wrap { y: false, ..self }
+    }
+
+    /// Only wraps along the Y axis.
+    pub fn y_only(self) -> Wrap {
+        Wrap { x: false, ..self }
+    }
+
+    pub fn origin(&self) -> Vec2 {
+        self.origin
+    }
+
+    pub fn size(&self) -> Vec2 {
+        self.size
+    }
+
+    pub fn contains(&self, position: Vec2) -> bool {
+        (self.origin.x..self.origin.x + self.size.x).contains(&position.x)
+            && (self.origin.y..self.origin.y + self.size.y).contains(&position.y)
+    }
+
+    /// Moves `position` by `delta`, wrapping around if `position` is in the
+    /// region. Returns `None` if the position would become negative.
+    pub fn offset(&self, position: Vec2, delta: Delta) -> Option<Vec2> {
+        let wrap = |wraps: bool, p: usize, d: isize, origin: usize, size: usize| {
+            if wraps && (origin..origin + size).contains(&p) {
+                let relative = (p - origin) as isize + d;
+                Some(origin + relative.rem_euclid(size as isize) as usize)
+            } else {
+                p.checked_add_signed(d)
+            }
+        };
+        let contained = self.contains(position);
+        Some(Vec2::new(
+            wrap(
+                self.x && contained,
+                position.x,
+                delta.x,
+                self.origin.x,
+                self.size.x,
+            )?,
+            wrap(
+                self.y && contained,
+                position.y,
+                delta.y,
+                self.origin.y,
+                self.size.y,
+            )?,
+        ))
+    }
+
+    /// Moves `position` one step in the direction of `offset`, wrapping around
+    /// if `position` is in the region.
+    pub fn neighbor(&self, position: Vec2, offset: Offset) -> Option<Vec2> {
+        self.offset(position, delta(offset))
+    }
+
+    /// Pairs `position` with this wrap, so that moving it with `Neighbor`,
+    /// `Neighbors` or `NeighborsAlong` wraps around. Note that
+    /// `neighbors_along` never ends along an axis that wraps.
+    pub fn at(self, position: impl Into<Vec2>) -> Wrapped {
+        Wrapped {
+            position: position.into(),
+            wrap: self,
+        }
+    }
+}
+
+/// A position that wraps around when moved, created by `Wrap::at`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Wrapped {
+    pub position: Vec2,
+    pub wrap: Wrap,
+}
+
+impl Neighbor for Wrapped {
+    fn neighbor(self, offset: Offset) -> Option<Self> {
+        Some(Wrapped {
+            position: self.wrap.neighbor(self.position, offset)?,
+            wrap: self.wrap,
+        })
+    }
+}
+
+/// A view of a `VecGrid` in which positions wrap around, created by
+/// `VecGrid::wrapping` or `VecGrid::wrapping_in`.
+#[derive(Debug)]
+pub struct WrappingGrid<'g, T> {
+    grid: &'g VecGrid<T>,
+    wrap: Wrap,
+}
+
+impl<'g, T> Clone for WrappingGrid<'g, T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<'g, T> Copy for WrappingGrid<'g, T> {}
+
+impl<T> VecGrid<T> {
+    /// Views the grid with positions wrapping around along both axes.
+    pub fn wrapping(&self) -> WrappingGrid<'_, T> {
+        self.wrapping_in(Wrap::new(self.size()))
+    }
+
+    /// Views the grid with positions wrapping around as described by `wrap`,
+    /// for example only within the region inside of the walls of a grid.
+    ///
+    /// # Panics
+    /// When the region does not fit in the grid.
+    pub fn wrapping_in(&self, wrap: Wrap) -> WrappingGrid<'_, T> {
+        let end = wrap.origin + wrap.size;
+        assert!(end.x <= self.width() && end.y <= self.height());
+        WrappingGrid { grid: self, wrap }
+    }
+}
+
+impl<'g, T> WrappingGrid<'g, T> {
+    pub fn grid(&self) -> &'g VecGrid<T> {
+        self.grid
+    }
+
+    pub fn wrap(&self) -> Wrap {
+        self.wrap
+    }
+
+    #[inline]
+    pub fn get<V: Into<Vec2>>(&self, index: V) -> Option<&'g T> {
+        self.grid.get(index)
+    }
+
+    /// The position one step from `position` in the direction of `offset`,
+    /// if it is in the grid.
+    pub fn neighbor(&self, position: Vec2, offset: Offset) -> Option<Vec2> {
+        self.wrap
+            .neighbor(position, offset)
+            .filter(|p| p.x < self.grid.width() && p.y < self.grid.height())
+    }
+
+    /// Looks up the cell that is at `position` at time `time`, when every cell
+    /// moves one step in the direction of `velocity` per unit of time, as the
+    /// cells in the grid are at time zero. Returns `None` if that cell would
+    /// have come from outside of the grid.
+    pub fn cell_at(&self, position: Vec2, velocity: Offset, time: usize) -> Option<&'g T> {
+        let delta = delta(velocity);
+        let time = time as isize;
+        let start = self
+            .wrap
+            .offset(position, Delta::new(-delta.x * time, -delta.y * time))?;
+        self.grid.get(start)
+    }
+}
+
+impl<'g, T, V: Into<Vec2>> Index<V> for WrappingGrid<'g, T> {
+    type Output = T;
+
+    #[inline]
+    fn index(&self, index: V) -> &Self::Output {
+        &self.grid[index]
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::offsets::NeighborsAlong;
+
+    #[test]
+    fn wrapping() {
+        let grid = VecGrid::new((4, 3), |p| p.y * 4 + p.x);
+        let torus = grid.wrapping();
+        assert_eq!(
+            Some(Vec2::new(3, 0)),
+            torus.neighbor(Vec2::new(0, 0), Offset::X_NEG)
+        );
+        assert_eq!(
+            Some(Vec2::new(3, 2)),
+            torus.neighbor(Vec2::new(0, 0), Offset::X_NEG_Y_NEG)
+        );
+        assert_eq!(Some(&7), torus.cell_at(Vec2::new(0, 1), Offset::X_POS, 1));
+        assert_eq!(Some(&7), torus.cell_at(Vec2::new(0, 1), Offset::X_POS, 5));
+        assert_eq!(Some(&10), torus.cell_at(Vec2::new(2, 0), Offset::Y_NEG, 2));
+
+        let positions = Wrap::new((4, 3))
+            .y_only()
+            .at((1, 1))
+            .neighbors_along(Offset::Y_POS)
+            .take(3)
+            .map(|w| w.position)
+            .collect::<Vec<_>>();
+        assert_eq!(
+            vec![Vec2::new(1, 2), Vec2::new(1, 0), Vec2::new(1, 1)],
+            positions
+        );
+        let x_only = Wrap::new((4, 3)).x_only();
+        assert_eq!(None, x_only.neighbor(Vec2::new(1, 0), Offset::Y_NEG));
+        assert_eq!(None, x_only.at((0, 0)).neighbor(Offset::Y_NEG));
+    }
+
+    #[test]
+    fn wrapping_inside_walls() {
+        // A 3x2 valley, surrounded by walls.
+        let grid = VecGrid::new((5, 4), |p| p.y * 5 + p.x);
+        let valley = grid.wrapping_in(Wrap::region((1, 1), (3, 2)));
+        assert_eq!(
+            Some(Vec2::new(3, 1)),
+            valley.neighbor(Vec2::new(1, 1), Offset::X_NEG)
+        );
+        assert_eq!(
+            Some(Vec2::new(2, 1)),
+            valley.neighbor(Vec2::new(2, 2), Offset::Y_POS)
+        );
+        // Walls don't wrap.
+        assert_eq!(
+            Some(Vec2::new(0, 0)),
+            valley.neighbor(Vec2::new(1, 0), Offset::X_NEG)
+        );
+        assert_eq!(None, valley.neighbor(Vec2::new(4, 0), Offset::X_POS));
+        assert_eq!(Some(&7), valley.cell_at(Vec2::new(1, 1), Offset::X_NEG, 1));
+        assert_eq!(Some(&12), valley.cell_at(Vec2::new(2, 2), Offset::Y_POS, 2));
+    }
+}
@@ -1,5 +1,6 @@
 #![feature(array_windows)]
 #![feature(auto_traits)]
+#![feature(byte_slice_trim_ascii)]
 #![feature(decl_macro)]
 #![feature(entry_insert)]
 #![feature(hash_raw_entry)]
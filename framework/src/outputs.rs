@@ -30,6 +30,10 @@ impl !NotIntoColorOutput for ColoredOutput {}
 impl !NotIntoColorOutput for AString {}
 #[allow(suspicious_auto_trait_impls)]
 impl !NotIntoColorOutput for &'static AStr {}
+// The negative impl for `AString` excludes every `Vec`, and with it the types
+// that contain one, such as the big integers.
+impl NotIntoColorOutput for num::BigInt {}
+impl NotIntoColorOutput for num::BigUint {}
 
 impl<T: fmt::Display + NotIntoColorOutput> From<T> for ColoredOutput {
     fn from(value: T) -> Self {
@@ -166,3 +170,36 @@ where
         }
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use num::BigInt;
+
+    /// Strips the ANSI escape sequences used for styling.
+    fn plain(output: ColoredOutput) -> String {
+        let mut value = String::new();
+        let mut chars = output.value().chars();
+        while let Some(c) = chars.next() {
+            match c {
+                '\x1b' => _ = chars.by_ref().find(|&c| c == 'm'),
+                c => value.push(c),
+            }
+        }
+        value
+    }
+
+    #[test]
+    fn big_int_outputs() {
+        let big = BigInt::from(u64::MAX);
+        assert_eq!(
+            "18446744073709551615 + 1 = 18446744073709551616",
+            plain(AddOutput([big.clone(), BigInt::from(1)]).into())
+        );
+        assert_eq!(
+            "18446744073709551615 * -2 = -36893488147419103230",
+            plain(MulOutput(vec![big.clone(), BigInt::from(-2)]).into())
+        );
+        assert_eq!("18446744073709551615", plain(big.into()));
+    }
+}
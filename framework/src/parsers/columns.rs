@@ -0,0 +1,188 @@
+use super::util::split_line;
+use super::*;
+
+type Slots<T> = Vec<Option<T>>;
+
+/// Applies the parser to each slot of a line, where a slot is `stride` bytes
+/// wide, and trimmed of whitespace. Blank slots, and slots beyond the end of
+/// the line, are `None`. Trailing `None`s are not included.
+fn parse_slots<'s, P: Parser<'s>>(
+    parser: &P,
+    line: &'s [u8],
+    stride: usize,
+    offset: usize,
+) -> Result<Slots<P::Output>, (ParseError, &'s [u8])> {
+    let mut cells = Vec::new();
+    let mut start = offset;
+    while start < line.len() {
+        let slot = line[start..line.len().min(start + stride)].trim_ascii();
+        cells.push(if slot.is_empty() {
+            None
+        } else {
+            match parser.parse(slot)? {
+                (cell, []) => Some(cell),
+                (_, remainder) => return Err((ParseError::UnexpectedChar, remainder)),
+            }
+        });
+        start += stride;
+    }
+    while let Some(None) = cells.last() {
+        cells.pop();
+    }
+    Ok(cells)
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct ColumnsParser<PC, PL> {
+    cell: PC,
+    label: PL,
+    stride: usize,
+    offset: usize,
+    labels_first: bool,
+}
+
+/// Parses a diagram which is laid out in columns, such as:
+/// ```text
+///     [D]
+/// [N] [C]
+/// [Z] [M] [P]
+///  1   2   3
+/// ```
+/// Every line is divided into slots of `stride` bytes, starting at `offset`.
+/// Each slot is trimmed of whitespace, and parsed using `cell`, where blank
+/// slots are considered missing. The diagram ends at an empty line, or the end
+/// of the input. Its last line is a row of labels, or its first line when
+/// using `labels_first`, which are parsed using `label`, and determine the
+/// number of columns. The label row is only recognized by its position, so
+/// labels may look like cells.
+///
+/// Returns each column's cells from bottom to top. Missing cells may only be
+/// above the top of a column, a cell above a missing cell is a
+/// `ParseError::GapInColumn`. Lines may be shorter than others. The newline
+/// after the last line is not consumed.
+pub fn columns<'s, PC, PL>(
+    cell: PC,
+    label: PL,
+    stride: usize,
+    offset: usize,
+) -> ColumnsParser<PC, PL>
+where
+    PC: Parser<'s>,
+    PL: Parser<'s>,
+{
+    assert!(stride > 0, "stride must be positive");
+    ColumnsParser {
+        cell,
+        label,
+        stride,
+        offset,
+        labels_first: false,
+    }
+}
+
+impl<PC, PL> ColumnsParser<PC, PL> {
+    /// Expects the row of labels above the cells, instead of below them.
+    pub fn labels_first(self) -> Self {
+        ColumnsParser {
+            labels_first: true,
+            ..self
+        }
+    }
+}
+
+impl<'s, PC, PL> Parser<'s> for ColumnsParser<PC, PL>
+where
+    PC: Parser<'s>,
+    PL: Parser<'s>,
+{
+    type Output = Vec<Vec<PC::Output>>;
+
+    fn parse(&self, input: &'s [u8]) -> ParseResult<'s, Self::Output> {
+        if input.is_empty() {
+            return Err((ParseError::EmptyInput, input));
+        }
+        let mut lines = Vec::new();
+        let mut remainder = input;
+        loop {
+            let (line, after_line) = split_line(remainder);
+            lines.push(line);
+            match after_line {
+                [b'\n', next @ ..] if !matches!(next, [] | [b'\n', ..]) => remainder = next,
+                _ => {
+                    remainder = after_line;
+                    break;
+                }
+            }
+        }
+        let (label_line, cell_lines) = if self.labels_first {
+            lines.split_first()
+        } else {
+            lines.split_last()
+        }
+        .expect("a diagram has at least one line");
+        let width = match parse_slots(&self.label, label_line, self.stride, self.offset) {
+            Ok(labels) if !labels.is_empty() => labels.len(),
+            _ => return Err((ParseError::MissingLabelRow, label_line)),
+        };
+        let rows = cell_lines
+            .iter()
+            .map(|&line| {
+                Ok((
+                    line,
+                    parse_slots(&self.cell, line, self.stride, self.offset)?,
+                ))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let mut columns: Vec<_> = (0..width).map(|_| Vec::new()).collect();
+        // Whether a column has reached its top, below which there is an empty
+        // slot, so that any cell above it would be floating.
+        let mut ended = vec![false; width];
+        for (line, cells) in rows.into_iter().rev() {
+            if cells.len() > width {
+                return Err((ParseError::GridCellAfterEndOfRowReached, line));
+            }
+            let cells = cells.into_iter().chain(std::iter::repeat_with(|| None));
+            for ((column, ended), cell) in columns.iter_mut().zip(&mut ended).zip(cells) {
+                match cell {
+                    Some(_) if *ended => return Err((ParseError::GapInColumn, line)),
+                    Some(cell) => column.push(cell),
+                    None => *ended = true,
+                }
+            }
+        }
+        Ok((columns, remainder))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    #[rustfmt::skip]
+    fn diagrams() {
+        let p = columns(delimited(token(b'['), any(), token(b']')), number::<u8>(), 4, 0);
+        assert_eq!( Ok((vec![vec![b'Z', b'N'], vec![b'M', b'C', b'D'], vec![b'P']], &b"\n\nmove"[..])),
+            p.parse(b"    [D]    \n[N] [C]\n[Z] [M] [P]\n 1   2   3 \n\nmove"));
+        assert_eq!( Ok((vec![vec![b'A'], vec![], vec![b'C']],    &b""[..])), p.parse(b"[A]     [C]\n 1   2   3"));
+        assert_eq!(Err((ParseError::GridCellAfterEndOfRowReached, &b"[A] [B]"[..])), p.parse(b"[A] [B]\n 1"));
+        assert_eq!(Err((ParseError::MissingLabelRow,             &b"[B]"[..])), p.parse(b"[A]\n[B]"));
+        assert_eq!(Err((ParseError::MissingLabelRow,             &b"move"[..])), p.parse(b"[A]\n 1\nmove"));
+        assert_eq!(Err((ParseError::UnexpectedChar,              &b"x"[..])), p.parse(b"[A]x\n 1"));
+        assert_eq!(Err((ParseError::GapInColumn,                 &b"[A] [B]"[..])), p.parse(b"[A] [B]\n    [C]\n 1   2"));
+        assert_eq!(Err((ParseError::GapInColumn,                 &b"    [B]"[..])), p.parse(b"    [B]\n[A]\n 1   2"));
+    }
+
+    #[test]
+    #[rustfmt::skip]
+    fn numeric_labels() {
+        // The cells could be parsed as labels, only the position tells them apart.
+        let p = columns(number::<u8>(), number::<u8>(), 3, 0);
+        assert_eq!(Ok((vec![vec![7, 5], vec![8, 6]], &b"\n\n9"[..])), p.parse(b" 5  6\n 7  8\n 1  2\n\n9"));
+        assert_eq!(Ok((vec![vec![], vec![]],         &b""[..])),       p.parse(b" 1  2"));
+        let p = p.labels_first();
+        assert_eq!(Ok((vec![vec![7, 5], vec![8, 6]], &b"\n\n9"[..])), p.parse(b" 1  2\n 5  6\n 7  8\n\n9"));
+        assert_eq!(Err((ParseError::GridCellAfterEndOfRowReached, &b" 5  6  7"[..])), p.parse(b" 1  2\n 5  6  7"));
+    }
+}
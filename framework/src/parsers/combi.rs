@@ -1,4 +1,6 @@
+use super::util::impl_for_tuples;
 use super::*;
+use crate::astr::AString;
 
 pub trait ParserCombiExt<'s>: Sized + Parser<'s> {
     /// Evaluates two parsers sequentially, and returns a tuple of their outputs
@@ -23,6 +25,15 @@ pub trait ParserCombiExt<'s>: Sized + Parser<'s> {
     fn map<T, F: Fn(Self::Output) -> T>(self, f: F) -> Map<Self, F> {
         Map(self, f)
     }
+    /// Like `map`, but also takes the inverse transformation, so that the
+    /// result can be used as a `Printer`
+    fn bimap<T, U, F: Fn(Self::Output) -> T, G: Fn(&T) -> U>(
+        self,
+        f: F,
+        inverse: G,
+    ) -> Bimap<Self, F, G> {
+        Bimap(self, f, inverse)
+    }
     /// Takes the output of one parser, and transforms it into a `Result` of another type
     fn map_res<T, F: Fn(Self::Output) -> Result<T, ParseError>>(self, f: F) -> MapRes<Self, F> {
         MapRes(self, f)
@@ -37,6 +48,126 @@ pub trait ParserCombiExt<'s>: Sized + Parser<'s> {
 
 impl<'s, P1: Parser<'s>> ParserCombiExt<'s> for P1 {}
 
+/// Evaluates two parsers sequentially, returns the output of the second
+pub fn preceded<'s, P1: Parser<'s>, P2: Parser<'s>>(prefix: P1, parser: P2) -> Then<P1, P2> {
+    Then(prefix, parser)
+}
+
+/// Evaluates three parsers sequentially, returns the output of the middle one
+pub fn delimited<'s, P1: Parser<'s>, P2: Parser<'s>, P3: Parser<'s>>(
+    open: P1,
+    parser: P2,
+    close: P3,
+) -> Trailed<Then<P1, P2>, P3> {
+    Trailed(Then(open, parser), close)
+}
+
+/// Evaluates three parsers sequentially, returns a tuple of the outputs of the
+/// first and the last one
+pub fn separated_pair<'s, P1: Parser<'s>, S: Parser<'s>, P2: Parser<'s>>(
+    first: P1,
+    separator: S,
+    second: P2,
+) -> And<Trailed<P1, S>, P2> {
+    And(Trailed(first, separator), second)
+}
+
+/// Attempts each parser in a tuple of parsers in order, returning the output of
+/// the first one that succeeds, or the error of the last one if none do
+pub fn alt<'s, T: Alternatives<'s>>(parsers: T) -> Alt<T> {
+    Alt(parsers)
+}
+
+pub trait Alternatives<'s> {
+    type Output: 's;
+    fn parse_alternatives(&self, input: &'s [u8]) -> ParseResult<'s, Self::Output>;
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct Alt<T>(T);
+impl<'s, T: Alternatives<'s>> Parser<'s> for Alt<T> {
+    type Output = T::Output;
+
+    fn parse(&self, input: &'s [u8]) -> ParseResult<'s, Self::Output> {
+        self.0.parse_alternatives(input)
+    }
+}
+
+/// Evaluates all parsers in the tuple sequentially, and returns a tuple of
+/// their outputs, and prints a tuple of values with the parsers in turn
+macro_rules! impl_tuple_parser {
+    ($($parser:ident $output:ident $value:ident),+) => {
+        impl<'s, $($parser: Parser<'s>),+> Parser<'s> for ($($parser,)+) {
+            type Output = ($($parser::Output,)+);
+
+            #[allow(non_snake_case)]
+            fn parse(&self, input: &'s [u8]) -> ParseResult<'s, Self::Output> {
+                let ($($parser,)+) = self;
+                let remainder = input;
+                $(let ($output, remainder) = $parser.parse(remainder)?;)+
+                Ok((($($output,)+), remainder))
+            }
+        }
+
+        impl<$($value,)+ $($parser: Printer<$value>),+> Printer<($($value,)+)> for ($($parser,)+) {
+            #[allow(non_snake_case)]
+            fn print(&self, ($($output,)+): &($($value,)+), output: &mut AString) {
+                let ($($parser,)+) = self;
+                $($parser.print($output, output);)+
+            }
+
+            #[allow(non_snake_case)]
+            fn can_print(&self, ($($output,)+): &($($value,)+)) -> bool {
+                let ($($parser,)+) = self;
+                true $(&& $parser.can_print($output))+
+            }
+        }
+    };
+}
+
+macro_rules! impl_alternatives {
+    ($first:ident $first_output:ident $first_value:ident $(, $parser:ident $output:ident $value:ident)*) => {
+        impl<'s, $first: Parser<'s>, $($parser: Parser<'s, Output = $first::Output>),*>
+            Alternatives<'s> for ($first, $($parser,)*)
+        {
+            type Output = $first::Output;
+
+            #[allow(non_snake_case)]
+            fn parse_alternatives(&self, input: &'s [u8]) -> ParseResult<'s, Self::Output> {
+                let ($first, $($parser,)*) = self;
+                $first.parse(input)$(.or_else(|_| $parser.parse(input)))*
+            }
+        }
+
+        /// Prints with the first alternative that can print the value, or
+        /// the last one if none can
+        impl<T, $first: Printer<T>, $($parser: Printer<T>),*> Printer<T>
+            for Alt<($first, $($parser,)*)>
+        {
+            #[allow(non_snake_case)]
+            fn print(&self, value: &T, output: &mut AString) {
+                let ($first, $($parser,)*) = &self.0;
+                let printer: &dyn Printer<T> = $first;
+                $(
+                    let printer: &dyn Printer<T> =
+                        if printer.can_print(value) { printer } else { $parser };
+                )*
+                printer.print(value, output);
+            }
+
+            #[allow(non_snake_case)]
+            fn can_print(&self, value: &T) -> bool {
+                let ($first, $($parser,)*) = &self.0;
+                $first.can_print(value) $(|| $parser.can_print(value))*
+            }
+        }
+    };
+}
+
+impl_for_tuples!(impl_tuple_parser);
+
+impl_for_tuples!(impl_alternatives);
+
 #[derive(Debug, Clone, Copy)]
 pub struct And<P1, P2>(P1, P2);
 impl<'s, P1: Parser<'s>, P2: Parser<'s>> Parser<'s> for And<P1, P2> {
@@ -49,6 +180,17 @@ impl<'s, P1: Parser<'s>, P2: Parser<'s>> Parser<'s> for And<P1, P2> {
     }
 }
 
+impl<T1, T2, P1: Printer<T1>, P2: Printer<T2>> Printer<(T1, T2)> for And<P1, P2> {
+    fn print(&self, (v1, v2): &(T1, T2), output: &mut AString) {
+        self.0.print(v1, output);
+        self.1.print(v2, output);
+    }
+
+    fn can_print(&self, (v1, v2): &(T1, T2)) -> bool {
+        self.0.can_print(v1) && self.1.can_print(v2)
+    }
+}
+
 #[derive(Debug, Clone, Copy)]
 pub struct Then<P1, P2>(P1, P2);
 impl<'s, P1: Parser<'s>, P2: Parser<'s>> Parser<'s> for Then<P1, P2> {
@@ -60,6 +202,17 @@ impl<'s, P1: Parser<'s>, P2: Parser<'s>> Parser<'s> for Then<P1, P2> {
     }
 }
 
+impl<T, P1: Printer<()>, P2: Printer<T>> Printer<T> for Then<P1, P2> {
+    fn print(&self, value: &T, output: &mut AString) {
+        self.0.print(&(), output);
+        self.1.print(value, output);
+    }
+
+    fn can_print(&self, value: &T) -> bool {
+        self.1.can_print(value)
+    }
+}
+
 #[derive(Debug, Clone, Copy)]
 pub struct Trailed<P1, P2>(P1, P2);
 impl<'s, P1: Parser<'s>, P2: Parser<'s>> Parser<'s> for Trailed<P1, P2> {
@@ -72,6 +225,17 @@ impl<'s, P1: Parser<'s>, P2: Parser<'s>> Parser<'s> for Trailed<P1, P2> {
     }
 }
 
+impl<T, P1: Printer<T>, P2: Printer<()>> Printer<T> for Trailed<P1, P2> {
+    fn print(&self, value: &T, output: &mut AString) {
+        self.0.print(value, output);
+        self.1.print(&(), output);
+    }
+
+    fn can_print(&self, value: &T) -> bool {
+        self.0.can_print(value)
+    }
+}
+
 #[derive(Debug, Clone, Copy)]
 pub struct Or<P1, P2>(P1, P2);
 impl<'s, P1: Parser<'s>, P2: Parser<'s, Output = P1::Output>> Parser<'s> for Or<P1, P2> {
@@ -82,6 +246,20 @@ impl<'s, P1: Parser<'s>, P2: Parser<'s, Output = P1::Output>> Parser<'s> for Or<
     }
 }
 
+impl<T, P1: Printer<T>, P2: Printer<T>> Printer<T> for Or<P1, P2> {
+    fn print(&self, value: &T, output: &mut AString) {
+        if self.0.can_print(value) {
+            self.0.print(value, output);
+        } else {
+            self.1.print(value, output);
+        }
+    }
+
+    fn can_print(&self, value: &T) -> bool {
+        self.0.can_print(value) || self.1.can_print(value)
+    }
+}
+
 #[derive(Debug, Clone, Copy)]
 pub struct Map<P, F>(P, F);
 impl<'s, P: Parser<'s>, T: 's, F: Fn(P::Output) -> T> Parser<'s> for Map<P, F> {
@@ -94,6 +272,28 @@ impl<'s, P: Parser<'s>, T: 's, F: Fn(P::Output) -> T> Parser<'s> for Map<P, F> {
     }
 }
 
+#[derive(Debug, Clone, Copy)]
+pub struct Bimap<P, F, G>(P, F, G);
+impl<'s, P: Parser<'s>, T: 's, F: Fn(P::Output) -> T, G> Parser<'s> for Bimap<P, F, G> {
+    type Output = T;
+
+    fn parse(&self, input: &'s [u8]) -> ParseResult<'s, T> {
+        self.0
+            .parse(input)
+            .map(|(value, remainder)| ((self.1)(value), remainder))
+    }
+}
+
+impl<T, U, P: Printer<U>, F, G: Fn(&T) -> U> Printer<T> for Bimap<P, F, G> {
+    fn print(&self, value: &T, output: &mut AString) {
+        self.0.print(&(self.2)(value), output);
+    }
+
+    fn can_print(&self, value: &T) -> bool {
+        self.0.can_print(&(self.2)(value))
+    }
+}
+
 #[derive(Debug, Clone, Copy)]
 pub struct MapRes<P, F>(P, F);
 impl<'s, P: Parser<'s>, T: 's, F: Fn(P::Output) -> Result<T, ParseError>> Parser<'s>
@@ -123,3 +323,46 @@ impl<'s, P: Parser<'s>> Parser<'s> for Opt<P> {
         })
     }
 }
+
+impl<T, P: Printer<T>> Printer<Option<T>> for Opt<P> {
+    fn print(&self, value: &Option<T>, output: &mut AString) {
+        if let Some(value) = value {
+            self.0.print(value, output);
+        }
+    }
+
+    fn can_print(&self, value: &Option<T>) -> bool {
+        match value {
+            Some(value) => self.0.can_print(value),
+            None => true,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    #[rustfmt::skip]
+    fn tuples() {
+        let triple = (number::<u8>(), token(b','), number::<i8>());
+        assert_eq!( Ok(((1, (), -2),                    &b""  [..])), triple.parse(b"1,-2"  ));
+        assert_eq!(Err((ParseError::TokenDoesNotMatch,  &b";2"[..])), triple.parse(b"1;2"   ));
+        let pair = separated_pair(number::<u8>(), token(b", "), number::<u8>());
+        assert_eq!( Ok(((3, 4),                         &b")" [..])), pair.parse(b"3, 4)"   ));
+        let wrapped = delimited(token(b'('), pair, token(b')'));
+        assert_eq!( Ok(((3, 4),                         &b""  [..])), wrapped.parse(b"(3, 4)"));
+        assert_eq!( Ok((5,                              &b""  [..])), preceded(token(b"x="), number::<u8>()).parse(b"x=5"));
+    }
+
+    #[test]
+    #[rustfmt::skip]
+    fn alternatives() {
+        let p = alt((token((b"a", 1)), token((b"b", 2)), number::<u8>()));
+        assert_eq!( Ok((1,                         &b"b"[..])), p.parse(b"ab" ));
+        assert_eq!( Ok((2,                         &b"" [..])), p.parse(b"b"  ));
+        assert_eq!( Ok((30,                        &b"" [..])), p.parse(b"30" ));
+        assert_eq!(Err((ParseError::ExpectedDigit, &b"c"[..])), p.parse(b"c"  ));
+    }
+}
@@ -1,4 +1,5 @@
 pub use super::*;
+use crate::astr::AString;
 
 #[derive(Debug, Clone, Copy)]
 pub struct Digit;
@@ -17,6 +18,17 @@ impl<'s> Parser<'s> for Digit {
     }
 }
 
+impl Printer<u8> for Digit {
+    fn print(&self, value: &u8, output: &mut AString) {
+        debug_assert!(*value < 10);
+        output.push(b'0' + value);
+    }
+
+    fn can_print(&self, value: &u8) -> bool {
+        *value < 10
+    }
+}
+
 pub macro pattern($p:pat) {{
     #[derive(Debug, Clone, Copy)]
     struct PatternParser;
@@ -53,6 +65,12 @@ impl<'s> Parser<'s> for Token<u8> {
     }
 }
 
+impl Printer<()> for Token<u8> {
+    fn print(&self, _: &(), output: &mut AString) {
+        output.push(self.value);
+    }
+}
+
 impl<'s, T: 's + Clone> Parser<'s> for Token<(u8, T)> {
     type Output = T;
     fn parse(&self, input: &'s [u8]) -> ParseResult<'s, T> {
@@ -65,6 +83,17 @@ impl<'s, T: 's + Clone> Parser<'s> for Token<(u8, T)> {
     }
 }
 
+impl<T: PartialEq> Printer<T> for Token<(u8, T)> {
+    fn print(&self, value: &T, output: &mut AString) {
+        assert!(self.can_print(value), "token does not produce this value");
+        output.push(self.value.0);
+    }
+
+    fn can_print(&self, value: &T) -> bool {
+        *value == self.value.1
+    }
+}
+
 impl<'s, 't: 's> Parser<'s> for Token<&'t [u8]> {
     type Output = ();
     fn parse(&self, input: &'s [u8]) -> ParseResult<'s, ()> {
@@ -76,6 +105,12 @@ impl<'s, 't: 's> Parser<'s> for Token<&'t [u8]> {
     }
 }
 
+impl Printer<()> for Token<&[u8]> {
+    fn print(&self, _: &(), output: &mut AString) {
+        output.extend_from_slice(self.value);
+    }
+}
+
 impl<'s, 't: 's, T: 's + Clone> Parser<'s> for Token<(&'t [u8], T)> {
     type Output = T;
     fn parse(&self, input: &'s [u8]) -> ParseResult<'s, T> {
@@ -87,6 +122,17 @@ impl<'s, 't: 's, T: 's + Clone> Parser<'s> for Token<(&'t [u8], T)> {
     }
 }
 
+impl<T: PartialEq> Printer<T> for Token<(&[u8], T)> {
+    fn print(&self, value: &T, output: &mut AString) {
+        assert!(self.can_print(value), "token does not produce this value");
+        output.extend_from_slice(self.value.0);
+    }
+
+    fn can_print(&self, value: &T) -> bool {
+        *value == self.value.1
+    }
+}
+
 impl<'s, 't: 's, const N: usize> Parser<'s> for Token<&'t [u8; N]> {
     type Output = ();
     fn parse(&self, input: &'s [u8]) -> ParseResult<'s, ()> {
@@ -98,6 +144,12 @@ impl<'s, 't: 's, const N: usize> Parser<'s> for Token<&'t [u8; N]> {
     }
 }
 
+impl<const N: usize> Printer<()> for Token<&[u8; N]> {
+    fn print(&self, _: &(), output: &mut AString) {
+        output.extend_from_slice(self.value);
+    }
+}
+
 impl<'s, 't: 's, T: 's + Clone, const N: usize> Parser<'s> for Token<(&'t [u8; N], T)> {
     type Output = T;
     fn parse(&self, input: &'s [u8]) -> ParseResult<'s, T> {
@@ -109,6 +161,17 @@ impl<'s, 't: 's, T: 's + Clone, const N: usize> Parser<'s> for Token<(&'t [u8; N
     }
 }
 
+impl<T: PartialEq, const N: usize> Printer<T> for Token<(&[u8; N], T)> {
+    fn print(&self, value: &T, output: &mut AString) {
+        assert!(self.can_print(value), "token does not produce this value");
+        output.extend_from_slice(self.value.0);
+    }
+
+    fn can_print(&self, value: &T) -> bool {
+        *value == self.value.1
+    }
+}
+
 pub fn token<T>(token: T) -> Token<T> {
     Token { value: token }
 }
@@ -125,6 +188,12 @@ impl<'s> Parser<'s> for Any {
         }
     }
 }
+impl Printer<u8> for Any {
+    fn print(&self, value: &u8, output: &mut AString) {
+        output.push(*value);
+    }
+}
+
 pub fn any() -> Any {
     Any
 }
@@ -132,6 +201,7 @@ pub fn any() -> Any {
 #[cfg(test)]
 mod test {
     use super::*;
+    use num::{BigInt, BigUint};
 
     #[test]
     #[rustfmt::skip]
@@ -158,4 +228,74 @@ mod test {
         assert_eq!(Err((ParseError::Overflow,      &b"-129"[..])), number::<i8>().parse(b"-129" ));
         assert_eq!(Err((ParseError::EmptyInput,    &b""    [..])), number::<i8>().parse(b""     ));
     }
+
+    #[test]
+    #[rustfmt::skip]
+    fn radix_numbers() {
+        assert_eq!( Ok((0xff,                      &b"g"  [..])), hex::<u8>().parse(b"fFg"  ));
+        assert_eq!( Ok((-0x80,                     &b""   [..])), hex::<i8>().parse(b"-80"  ));
+        assert_eq!(Err((ParseError::Overflow,      &b"100"[..])), hex::<u8>().parse(b"100"  ));
+        assert_eq!( Ok((0o17,                      &b"8"  [..])), oct::<u8>().parse(b"178"  ));
+        assert_eq!( Ok((0b101,                     &b"2"  [..])), bin::<u8>().parse(b"1012" ));
+        assert_eq!(Err((ParseError::ExpectedDigit, &b"2"  [..])), bin::<u8>().parse(b"2"    ));
+    }
+
+    #[test]
+    #[rustfmt::skip]
+    fn separated_numbers() {
+        assert_eq!( Ok((1_000_000,                 &b""   [..])), number::<u32>().separated().parse(b"1_000_000"));
+        assert_eq!( Ok((-1_000,                    &b""   [..])), number::<i32>().separated().parse(b"-1_000"   ));
+        assert_eq!( Ok((1,                         &b"__2"[..])), number::<u32>().separated().parse(b"1__2"     ));
+        assert_eq!( Ok((1,                         &b"_"  [..])), number::<u32>().separated().parse(b"1_"       ));
+        assert_eq!( Ok((1,                         &b"_2" [..])), number::<u32>().parse(b"1_2"                  ));
+        assert_eq!(Err((ParseError::ExpectedDigit, &b"_1" [..])), number::<u32>().separated().parse(b"_1"       ));
+        assert_eq!( Ok((0xdead_beef,               &b""   [..])), hex::<u32>().separated().parse(b"dead_beef"   ));
+    }
+
+    #[test]
+    #[rustfmt::skip]
+    fn fixed_numbers() {
+        assert_eq!( Ok((12,                        &b"34" [..])), number::<u8>().fixed::<2>().parse(b"1234"));
+        assert_eq!( Ok((7,                         &b""   [..])), number::<i8>().fixed::<3>().parse(b"007" ));
+        assert_eq!(Err((ParseError::ExpectedDigit, &b"1a" [..])), number::<u8>().fixed::<2>().parse(b"1a"  ));
+        assert_eq!(Err((ParseError::ExpectedDigit, &b"1"  [..])), number::<u8>().fixed::<2>().parse(b"1"   ));
+        assert_eq!(Err((ParseError::EmptyInput,    &b""   [..])), number::<u8>().fixed::<2>().parse(b""    ));
+        assert_eq!(Err((ParseError::Overflow,      &b"256"[..])), number::<u8>().fixed::<3>().parse(b"256" ));
+        assert_eq!(Err((ParseError::Overflow,      &b"128"[..])), number::<i8>().fixed::<3>().parse(b"128" ));
+        assert_eq!( Ok((0xbeef,                    &b"0"  [..])), hex::<u16>().fixed::<4>().parse(b"beef0" ));
+        assert_eq!( Ok((1,                         &b"_0" [..])), number::<u8>().fixed::<1>().parse(b"1_0" ));
+        assert_eq!( Ok((10_00,                     &b"_0" [..])), number::<u32>().separated().fixed::<4>().parse(b"10_00_0"));
+        assert_eq!(Err((ParseError::ExpectedDigit, &b"1_" [..])), number::<u8>().separated().fixed::<2>().parse(b"1_"));
+        assert_eq!( Ok((num::BigUint::from(1_234u32), &b""[..])), number::<num::BigUint>().separated().fixed::<4>().parse(b"1_234"));
+    }
+
+    #[test]
+    #[rustfmt::skip]
+    fn float_numbers() {
+        assert_eq!( Ok((1.5,                       &b""   [..])), number::<f64>().parse(b"1.5"     ));
+        assert_eq!( Ok((-0.25,                     &b""   [..])), number::<f64>().parse(b"-.25"    ));
+        assert_eq!( Ok((1e3,                       &b""   [..])), number::<f64>().parse(b"1e3"     ));
+        assert_eq!( Ok((-2.5e-3,                   &b""   [..])), number::<f32>().parse(b"-2.5E-3" ));
+        assert_eq!( Ok((1.0,                       &b"."  [..])), number::<f64>().parse(b"1."      ));
+        assert_eq!( Ok((2.0,                       &b"e"  [..])), number::<f64>().parse(b"2e"      ));
+        assert_eq!( Ok((1e6,                       &b""   [..])), number::<f64>().separated().parse(b"1_000.0e0_3"));
+        assert_eq!(Err((ParseError::ExpectedDigit, &b"-." [..])), number::<f64>().parse(b"-."      ));
+        assert_eq!(Err((ParseError::EmptyInput,    &b""   [..])), number::<f64>().parse(b""        ));
+    }
+
+    #[test]
+    #[rustfmt::skip]
+    fn big_numbers() {
+        let big = |s: &str| s.parse::<BigInt>().unwrap();
+        assert_eq!( Ok((big("-340282366920938463463374607431768211456"), &b"x"[..])), number::<BigInt>().parse(b"-340282366920938463463374607431768211456x"));
+        assert_eq!( Ok((big("1000000"),                     &b""   [..])), number::<BigInt>().separated().parse(b"+1_000_000"));
+        assert_eq!( Ok((BigUint::from(u128::MAX) + 1u8,     &b""   [..])), hex::<BigUint>().parse(b"100000000000000000000000000000000"));
+        assert_eq!( Ok((BigUint::from(12u8),                &b"34" [..])), number::<BigUint>().fixed::<2>().parse(b"1234"));
+        assert_eq!(Err((ParseError::ExpectedDigit,          &b"-1" [..])), number::<BigUint>().parse(b"-1"));
+        assert_eq!(Err((ParseError::ExpectedDigit,          &b"1a" [..])), number::<BigInt>().fixed::<2>().parse(b"1a"));
+        assert_eq!(Err((ParseError::EmptyInput,             &b""   [..])), number::<BigInt>().parse(b"-"));
+        assert_eq!(b"-ff".to_vec(), hex::<BigInt>().print_to_string(&big("-255")));
+        assert_eq!(b"007".to_vec(), number::<BigUint>().fixed::<3>().print_to_string(&BigUint::from(7u8)));
+        assert_round_trip(&number::<BigInt>(), &(big("-3") << 200));
+    }
 }
@@ -0,0 +1,150 @@
+use super::*;
+use crate::{astr::AString, offsets::Offset};
+use std::fmt::{self, Display};
+
+/// Which way the Y axis points, as a result, which offset "up" maps to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum YAxis {
+    /// Up is `Offset::Y_POS`, like in mathematics.
+    Up,
+    /// Up is `Offset::Y_NEG`, like in a grid where rows are numbered from top
+    /// to bottom.
+    Down,
+}
+
+/// Parses a single direction symbol into an `Offset`, created by `udlr`,
+/// `arrows` or `compass`. Can also print an `Offset` back into its symbol.
+#[derive(Debug, Clone, Copy)]
+pub struct DirectionParser {
+    /// The symbols for up, right, down and left, in that order.
+    symbols: [u8; 4],
+    y_axis: YAxis,
+}
+
+/// Parses `U`, `R`, `D` and `L`, with the Y axis pointing down.
+pub const fn udlr() -> DirectionParser {
+    DirectionParser::new(*b"URDL")
+}
+
+/// Parses `^`, `>`, `v` and `<`, with the Y axis pointing down.
+pub const fn arrows() -> DirectionParser {
+    DirectionParser::new(*b"^>v<")
+}
+
+/// Parses `N`, `E`, `S` and `W`, with the Y axis pointing down.
+pub const fn compass() -> DirectionParser {
+    DirectionParser::new(*b"NESW")
+}
+
+impl DirectionParser {
+    const fn new(symbols: [u8; 4]) -> Self {
+        DirectionParser {
+            symbols,
+            y_axis: YAxis::Down,
+        }
+    }
+
+    /// Maps up to `Offset::Y_POS`.
+    pub const fn y_up(self) -> Self {
+        self.y_axis(YAxis::Up)
+    }
+
+    /// Maps up to `Offset::Y_NEG`, this is the default.
+    pub const fn y_down(self) -> Self {
+        self.y_axis(YAxis::Down)
+    }
+
+    pub const fn y_axis(self, y_axis: YAxis) -> Self {
+        DirectionParser {
+            symbols: self.symbols,
+            y_axis,
+        }
+    }
+
+    /// The offsets corresponding to the symbols for up, right, down and left.
+    const fn offsets(&self) -> [Offset; 4] {
+        let up = match self.y_axis {
+            YAxis::Up => Offset::Y_POS,
+            YAxis::Down => Offset::Y_NEG,
+        };
+        [up, Offset::X_POS, up.flip_y(), Offset::X_NEG]
+    }
+
+    pub fn offset(&self, symbol: u8) -> Option<Offset> {
+        let index = self.symbols.iter().position(|&s| s == symbol)?;
+        Some(self.offsets()[index])
+    }
+
+    /// The inverse of `offset`, returns `None` if the offset is not orthogonal.
+    pub fn symbol(&self, offset: Offset) -> Option<u8> {
+        let index = self.offsets().iter().position(|&o| o == offset)?;
+        Some(self.symbols[index])
+    }
+
+    /// Formats an orthogonal offset as its symbol.
+    ///
+    /// # Panics
+    /// When displaying an offset which is not orthogonal.
+    pub fn display(self, offset: Offset) -> DisplayDirection {
+        DisplayDirection {
+            parser: self,
+            offset,
+        }
+    }
+}
+
+impl<'s> Parser<'s> for DirectionParser {
+    type Output = Offset;
+
+    fn parse(&self, input: &'s [u8]) -> ParseResult<'s, Self::Output> {
+        let &symbol = input.first().ok_or((ParseError::EmptyInput, input))?;
+        match self.offset(symbol) {
+            Some(offset) => Ok((offset, &input[1..])),
+            None => Err((ParseError::TokenDoesNotMatch, input)),
+        }
+    }
+}
+
+impl Printer<Offset> for DirectionParser {
+    fn print(&self, value: &Offset, output: &mut AString) {
+        output.push(self.symbol(*value).expect("offset is not orthogonal"));
+    }
+
+    fn can_print(&self, value: &Offset) -> bool {
+        self.symbol(*value).is_some()
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct DisplayDirection {
+    parser: DirectionParser,
+    offset: Offset,
+}
+
+impl Display for DisplayDirection {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let symbol = self
+            .parser
+            .symbol(self.offset)
+            .expect("offset is not orthogonal");
+        write!(f, "{}", symbol as char)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    #[rustfmt::skip]
+    fn directions() {
+        assert_eq!( Ok((Offset::Y_NEG,                  &b"R"[..])), udlr().parse(b"UR"));
+        assert_eq!( Ok((Offset::Y_POS,                  &b""[..] )), udlr().y_up().parse(b"U"));
+        assert_eq!( Ok((Offset::Y_POS,                  &b""[..] )), arrows().parse(b"v"));
+        assert_eq!( Ok((Offset::X_NEG,                  &b""[..] )), compass().parse(b"W"));
+        assert_eq!(Err((ParseError::TokenDoesNotMatch,  &b"x"[..])), compass().parse(b"x"));
+        assert_eq!(Some(b'S'), compass().y_up().symbol(Offset::Y_NEG));
+        assert_eq!(None,       compass().symbol(Offset::X_POS_Y_POS));
+        assert_eq!("^>v<", [Offset::Y_NEG, Offset::X_POS, Offset::Y_POS, Offset::X_NEG].map(|o| arrows().display(o).to_string()).concat());
+    }
+}
@@ -18,10 +18,58 @@ pub enum ParseError {
     TokenDoesNotMatch,
     #[error("unexpected char")]
     UnexpectedChar,
+    #[error("section not fully consumed")]
+    SectionNotConsumed,
+    #[error("line not fully consumed")]
+    LineNotConsumed,
+    #[error("line {0}: {1}")]
+    AtLine(usize, Box<ParseError>),
+    #[error("line {0}, column {1}: {2}")]
+    AtPosition(usize, usize, Box<ParseError>),
+    #[error("line is longer than {0} bytes")]
+    LineTooLong(usize),
+    #[error("{}", join_errors(.0))]
+    Multiple(Vec<ParseError>),
+    #[error("expected a field")]
+    ExpectedField,
+    #[error("unknown field {0:?}")]
+    UnknownField(String),
+    #[error("duplicate field {0:?}")]
+    DuplicateField(String),
+    #[error("missing field {0:?}")]
+    MissingField(String),
+    #[error("field not fully consumed")]
+    FieldNotConsumed,
+    #[error("identifier {0:?} is defined more than once")]
+    DuplicateIdentifier(String),
+    #[error("identifier {0:?} is referenced, but never defined")]
+    UndefinedIdentifier(String),
+    #[error("expected a row of labels")]
+    MissingLabelRow,
+    #[error("a column has an empty slot below a cell")]
+    GapInColumn,
     #[error("a cell was parsed that is beyond established width of the grid")]
     GridCellAfterEndOfRowReached,
     #[error("a row was incomplete")]
     GridIncompleteRow,
+    #[error("a layer differs in size from the first layer")]
+    GridLayerSizeMismatch,
+    #[error("marker {:?} is missing", *.0 as char)]
+    MissingMarker(u8),
+    #[error("marker {:?} occurs more than once", *.0 as char)]
+    DuplicateMarker(u8),
+    #[error("expected {0} items, found {1}")]
+    CountMismatch(usize, usize),
+    #[error("recursion depth limit exceeded")]
+    RecursionLimitExceeded,
     #[error("{0}")]
     Custom(&'static str),
 }
+
+fn join_errors(errors: &[ParseError]) -> String {
+    errors
+        .iter()
+        .map(ToString::to_string)
+        .collect::<Vec<_>>()
+        .join("\n")
+}
@@ -0,0 +1,48 @@
+#[doc(hidden)]
+pub use macros::parse_format as __parse_format;
+
+/// Creates a parser from a template, in which placeholders are substituted by
+/// parsers for their type. Literal text must match exactly, and `{{` and `}}`
+/// match a single `{` or `}`. The output is a tuple of the placeholder values.
+///
+/// Supported placeholders:
+/// - `{i32}`, `{u8}`, etc: any integer type, parsed through `number`.
+/// - `{f32}`, `{f64}`: a floating point number, parsed through `number`.
+/// - `{u32:hex}`, `{u8:oct}`, `{u16:bin}`, etc: an integer in another radix.
+/// - `{u8:digit}`, etc: a single digit, converted to the integer type.
+/// - `{word}`: one or more ASCII letters, as a `&[u8]`.
+/// - `{byte}`: any single byte.
+///
+/// Placeholders name the type itself, type aliases are not resolved.
+///
+/// ```
+/// use advent_of_code_framework::parsers::*;
+///
+/// let scan = parse_format!("Sensor at x={i32}, y={i32}: closest beacon is at x={i32}, y={i32}");
+/// let line = b"Sensor at x=2, y=18: closest beacon is at x=-2, y=15";
+/// assert_eq!(Ok(((2, 18, -2, 15), &b""[..])), scan.parse(line));
+/// ```
+pub macro parse_format($template:literal) {
+    $crate::parsers::format::__parse_format!($crate, $template)
+}
+
+#[cfg(test)]
+mod test {
+    use super::super::*;
+
+    #[test]
+    #[rustfmt::skip]
+    fn templates() {
+        let p = parse_format!("x={i32}, y={i32}");
+        assert_eq!( Ok(((-3, 4),                       &b""   [..])), p.parse(b"x=-3, y=4"));
+        assert_eq!(Err((ParseError::TokenDoesNotMatch, &b"; y"[..])), p.parse(b"x=-3; y"  ));
+        let p = parse_format!("{word} {u8:digit}{{{byte}}}");
+        assert_eq!( Ok(((&b"abc"[..], 7, b'!'),        &b"\n" [..])), p.parse(b"abc 7{!}\n"));
+        let p = parse_format!("Blueprint {usize}: \
+            Each ore robot costs {u32} ore.");
+        assert_eq!( Ok(((1, 4),                        &b""   [..])), p.parse(b"Blueprint 1: Each ore robot costs 4 ore."));
+        assert_eq!( Ok(((),                            &b""   [..])), parse_format!("\x41").parse(b"A"));
+        let p = parse_format!("{f64}/{u16:hex}");
+        assert_eq!( Ok(((0.5, 0xff),                   &b""   [..])), p.parse(b"0.5/ff"));
+    }
+}
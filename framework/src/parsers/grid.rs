@@ -1,6 +1,10 @@
-use super::Parser;
-use crate::grid::{Grid, GridBuilder};
+use super::{ParseError, ParseResult, Parser, Printer};
+use crate::astr::AString;
+use crate::grid::{Grid, GridBuilder, RaggedGridBuilder, VecGrid, VecGrid3, VecGridBuilder};
 use std::marker::PhantomData;
+use std::ops::Range;
+
+type Vec2 = crate::vecs::Vec2<usize>;
 
 pub fn grid<G, PC, PN>(cell: PC, line_separator: PN) -> GridParser<G, PC, PN> {
     GridParser {
@@ -17,6 +21,119 @@ pub struct GridParser<G, PC, PN> {
     _g: PhantomData<G>,
 }
 
+/// Parses a 3D grid from layers of text, each of which is parsed like `grid`.
+/// All layers must be the same size. A layer consumes the line separator after
+/// its last row, so layers separated by empty lines use `token(b'\n')` for both
+/// separators. Fails if a layer separator is not followed by a valid layer,
+/// unless it is at the end of the input.
+pub fn grid3<PC, PN, PL>(
+    cell: PC,
+    line_separator: PN,
+    layer_separator: PL,
+) -> Grid3Parser<PC, PN, PL> {
+    Grid3Parser {
+        cell,
+        line_separator,
+        layer_separator,
+    }
+}
+
+#[derive(Clone, Copy)]
+pub struct Grid3Parser<PC, PN, PL> {
+    cell: PC,
+    line_separator: PN,
+    layer_separator: PL,
+}
+
+#[derive(Clone, Copy)]
+pub struct RaggedGridParser<G, PC, PN, T> {
+    grid: GridParser<G, PC, PN>,
+    padding: T,
+}
+
+#[derive(Clone, Copy)]
+pub struct MarkedGridParser<G, PC, PN, T, const N: usize> {
+    grid: GridParser<G, PC, PN>,
+    markers: [(u8, T); N],
+}
+
+impl<G, PC, PN> GridParser<G, PC, PN> {
+    /// Allows rows of different lengths, rows shorter than the longest row are
+    /// padded with `padding`. Returns the grid, and the extent of each row, see
+    /// `RaggedGridBuilder::finish_ragged`.
+    pub fn ragged<T>(self, padding: T) -> RaggedGridParser<G, PC, PN, T> {
+        RaggedGridParser {
+            grid: self,
+            padding,
+        }
+    }
+
+    /// Records the positions of the given marker bytes, each of which must
+    /// occur exactly once in the grid. In the grid, a marker is replaced by the
+    /// cell value it is paired with. Returns the grid, and the position of each
+    /// marker, in the same order as the markers. The cell parser is tried
+    /// first, so a marker is only recognized where no cell can be parsed, and
+    /// marker bytes may start a cell.
+    pub fn markers<T, const N: usize>(
+        self,
+        markers: [(u8, T); N],
+    ) -> MarkedGridParser<G, PC, PN, T, N> {
+        MarkedGridParser {
+            grid: self,
+            markers,
+        }
+    }
+}
+
+/// Parses a grid into `builder`, `cell` is called with the position of the
+/// cell that is being parsed. The builder is completed with `finish`.
+fn parse_grid<'s, B, T, O, PN>(
+    input: &'s [u8],
+    mut builder: B,
+    mut cell: impl FnMut(&'s [u8], Vec2) -> ParseResult<'s, T>,
+    line_separator: &PN,
+    finish: impl FnOnce(B) -> Result<O, ParseError>,
+) -> ParseResult<'s, O>
+where
+    B: GridBuilder<T>,
+    O: 's,
+    PN: Parser<'s>,
+{
+    let mut position = Vec2::zero();
+
+    let (first_cell, mut remainder) = cell(input, position)?;
+    builder.push_cell(first_cell).map_err(|e| (e, input))?;
+    position.x += 1;
+
+    let mut any_cells_parsed = true;
+    loop {
+        while let Ok((cell, new_remainder)) = cell(remainder, position) {
+            any_cells_parsed = true;
+            builder.push_cell(cell).map_err(|e| (e, input))?;
+            remainder = new_remainder;
+            position.x += 1;
+        }
+
+        let before_newline = remainder;
+        let mut parse_next_line = || {
+            line_separator
+                .parse(remainder)
+                .map(|(_, new_remainder)| {
+                    remainder = new_remainder;
+                })
+                .is_ok()
+        };
+        if !any_cells_parsed || !parse_next_line() {
+            return finish(builder)
+                .map(|v| (v, before_newline))
+                .map_err(|e| (e, input));
+        }
+        builder.advance_next_line().map_err(|e| (e, input))?;
+        any_cells_parsed = false;
+        position = Vec2::new(0, position.y + 1);
+    }
+}
+
 impl<'s, G, T, PC, PN, NO> Parser<'s> for GridParser<G, PC, PN>
 where
     G: Grid<T> + 's,
@@ -25,37 +142,195 @@ where
 {
     type Output = G;
 
-    fn parse(&self, input: &'s [u8]) -> super::ParseResult<'s, Self::Output> {
-        let mut builder = G::Builder::new();
+    fn parse(&self, input: &'s [u8]) -> ParseResult<'s, Self::Output> {
+        parse_grid(
+            input,
+            G::Builder::new(),
+            |input, _| self.cell.parse(input),
+            &self.line_separator,
+            GridBuilder::finish,
+        )
+    }
+}
+
+impl<T, PC, PN> Printer<VecGrid<T>> for GridParser<VecGrid<T>, PC, PN>
+where
+    PC: Printer<T>,
+    PN: Printer<()>,
+{
+    fn print(&self, value: &VecGrid<T>, output: &mut AString) {
+        for (y, row) in value.cells().chunks(value.width()).enumerate() {
+            if y != 0 {
+                self.line_separator.print(&(), output);
+            }
+            for cell in row {
+                self.cell.print(cell, output);
+            }
+        }
+    }
+
+    fn can_print(&self, value: &VecGrid<T>) -> bool {
+        value.cells().iter().all(|cell| self.cell.can_print(cell))
+    }
+}
 
-        let (cell, mut remainder) = self.cell.parse(input)?;
-        builder.push_cell(cell).map_err(|e| (e, input))?;
+impl<'s, T, PC, PN, PL, NO, LO> Parser<'s> for Grid3Parser<PC, PN, PL>
+where
+    T: 's,
+    PC: Parser<'s, Output = T>,
+    PN: Parser<'s, Output = NO>,
+    PL: Parser<'s, Output = LO>,
+{
+    type Output = VecGrid3<T>;
 
-        let mut any_cells_parsed = true;
-        loop {
-            while let Ok((cell, new_remainder)) = self.cell.parse(remainder) {
-                any_cells_parsed = true;
-                builder.push_cell(cell).map_err(|e| (e, input))?;
-                remainder = new_remainder;
+    fn parse(&self, input: &'s [u8]) -> ParseResult<'s, Self::Output> {
+        let parse_layer = |input| {
+            parse_grid(
+                input,
+                VecGridBuilder::new(),
+                |input, _| self.cell.parse(input),
+                &self.line_separator,
+                VecGridBuilder::finish,
+            )
+        };
+        let (first, mut remainder) = parse_layer(input)?;
+        let size = first.size();
+        let mut layers = vec![first];
+        // A separator at the end of the input is left over, rather than
+        // starting another layer.
+        while let Ok((_, after_separator)) = self.layer_separator.parse(remainder) {
+            if after_separator.is_empty() {
+                break;
+            }
+            let (layer, after_layer) = parse_layer(after_separator)?;
+            if layer.size() != size {
+                return Err((ParseError::GridLayerSizeMismatch, after_separator));
             }
+            layers.push(layer);
+            remainder = after_layer;
+        }
+        Ok((VecGrid3::from_layers(layers), remainder))
+    }
+}
+
+impl<'s, G, T, PC, PN, NO> Parser<'s> for RaggedGridParser<G, PC, PN, T>
+where
+    G: Grid<T> + 's,
+    G::Builder: RaggedGridBuilder<T>,
+    T: Clone,
+    PC: Parser<'s, Output = T>,
+    PN: Parser<'s, Output = NO>,
+{
+    type Output = (G, Vec<Range<usize>>);
+
+    fn parse(&self, input: &'s [u8]) -> ParseResult<'s, Self::Output> {
+        parse_grid(
+            input,
+            G::Builder::ragged(self.padding.clone()),
+            |input, _| self.grid.cell.parse(input),
+            &self.grid.line_separator,
+            RaggedGridBuilder::finish_ragged,
+        )
+    }
+}
 
-            let before_newline = remainder;
-            let mut parse_next_line = || {
-                self.line_separator
-                    .parse(remainder)
-                    .map(|(_, new_remainder)| {
-                        remainder = new_remainder;
-                    })
-                    .is_ok()
+impl<'s, G, T, PC, PN, NO, const N: usize> Parser<'s> for MarkedGridParser<G, PC, PN, T, N>
+where
+    G: Grid<T> + 's,
+    T: Clone,
+    PC: Parser<'s, Output = T>,
+    PN: Parser<'s, Output = NO>,
+{
+    type Output = (G, [Vec2; N]);
+
+    fn parse(&self, input: &'s [u8]) -> ParseResult<'s, Self::Output> {
+        let mut positions = [None; N];
+        let mut duplicate = None;
+        let cell = |input: &'s [u8], position| {
+            let error = match self.grid.cell.parse(input) {
+                Ok(result) => return Ok(result),
+                Err(error) => error,
+            };
+            let marker = input
+                .first()
+                .and_then(|&c| self.markers.iter().position(|&(m, _)| m == c));
+            let Some(index) = marker else {
+                return Err(error);
             };
-            if !any_cells_parsed || !parse_next_line() {
-                return builder
-                    .finish()
-                    .map(|v| (v, before_newline))
-                    .map_err(|e| (e, input));
+            if positions[index].replace(position).is_some() && duplicate.is_none() {
+                duplicate = Some((self.markers[index].0, input));
             }
-            builder.advance_next_line().map_err(|e| (e, input))?;
-            any_cells_parsed = false;
+            Ok((self.markers[index].1.clone(), &input[1..]))
+        };
+        let (grid, remainder) = parse_grid(
+            input,
+            G::Builder::new(),
+            cell,
+            &self.grid.line_separator,
+            GridBuilder::finish,
+        )?;
+
+        if let Some((marker, at)) = duplicate {
+            return Err((ParseError::DuplicateMarker(marker), at));
+        }
+        if let Some(index) = positions.iter().position(Option::is_none) {
+            return Err((ParseError::MissingMarker(self.markers[index].0), input));
         }
+        Ok(((grid, positions.map(Option::unwrap)), remainder))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::parsers::*;
+    use crate::vecs::Vec3;
+
+    #[test]
+    #[rustfmt::skip]
+    fn markers() {
+        let p = grid::<VecGrid<u8>, _, _>(pattern!(b'a'..=b'z'), token(b'\n'))
+            .markers([(b'S', b'a'), (b'E', b'z')]);
+        let (grid, positions) = p.parse(b"Sbc\nabE\n").unwrap().0;
+        assert_eq!([Vec2::new(0, 0), Vec2::new(2, 1)], positions);
+        assert_eq!("abc\nabz", grid.stringify(|&c| c as char));
+        assert_eq!(Err((ParseError::MissingMarker(b'E'),   &b"Sbc\nabc"[..])), p.parse(b"Sbc\nabc").map(|_| ()));
+        assert_eq!(Err((ParseError::DuplicateMarker(b'S'), &b"SEa"   [..])), p.parse(b"Sbc\nSEa").map(|_| ()));
+    }
+
+    #[test]
+    fn markers_starting_cells() {
+        let p =
+            grid::<VecGrid<u8>, _, _>(token(b'#').then(digit()), token(b'\n')).markers([(b'#', 0)]);
+        let (grid, positions) = p.parse(b"#1#\n#2#3").unwrap().0;
+        assert_eq!([Vec2::new(1, 0)], positions);
+        assert_eq!("10\n23", grid.stringify(|&d| (b'0' + d) as char));
+    }
+
+    #[test]
+    fn layers() {
+        let p = grid3(pattern!(b'a'..=b'z'), token(b'\n'), token(b'\n'));
+        let (grid, remainder) = p.parse(b"ab\ncd\n\nef\ngh\n\n").unwrap();
+        assert_eq!(Vec3::new(2, 2, 2), grid.size());
+        assert_eq!(b'g', grid[(0, 1, 1)]);
+        assert_eq!("ab\ncd\n\nef\ngh", grid.stringify(|&c| c as char));
+        assert_eq!(b"\n", remainder);
+        assert_eq!(
+            Err((ParseError::GridLayerSizeMismatch, &b"ef"[..])),
+            p.parse(b"ab\ncd\n\nef")
+        );
+        assert_eq!(
+            Err((ParseError::UnexpectedChar, &b"E\ngh"[..])),
+            p.parse(b"ab\ncd\n\nE\ngh")
+        );
+    }
+
+    #[test]
+    fn ragged() {
+        let p = grid::<VecGrid<u8>, _, _>(pattern!(b'a'..=b'z' | b'.'), token(b'\n')).ragged(b'.');
+        let ((grid, extents), remainder) = p.parse(b"ab\n..cd\n...\n.\n\nc").unwrap();
+        assert_eq!("ab..\n..cd\n....\n....", grid.stringify(|&c| c as char));
+        assert_eq!(vec![0..2, 2..4, 3..3, 1..1], extents);
+        assert_eq!(b"\nc", remainder);
     }
 }
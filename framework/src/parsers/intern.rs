@@ -0,0 +1,277 @@
+use super::*;
+use crate::prelude::HashMap;
+use std::cell::RefCell;
+
+fn identifier(name: &[u8]) -> String {
+    String::from_utf8_lossy(name).into_owned()
+}
+
+#[derive(Debug, Default)]
+struct Table<'s> {
+    ids: HashMap<&'s [u8], u32>,
+    names: Vec<&'s [u8]>,
+    defined: Vec<bool>,
+    /// The ids in the order in which they were defined, so that definitions
+    /// can be rolled back.
+    definitions: Vec<u32>,
+}
+
+/// The size of a `Table` at some point, which it can be rolled back to.
+#[derive(Debug, Clone, Copy)]
+struct Checkpoint {
+    names: usize,
+    definitions: usize,
+}
+
+impl<'s> Table<'s> {
+    fn intern(&mut self, name: &'s [u8]) -> u32 {
+        *self.ids.entry(name).or_insert_with(|| {
+            let id = self.names.len() as u32;
+            self.names.push(name);
+            self.defined.push(false);
+            id
+        })
+    }
+
+    fn checkpoint(&self) -> Checkpoint {
+        Checkpoint {
+            names: self.names.len(),
+            definitions: self.definitions.len(),
+        }
+    }
+
+    /// Undoes the references and definitions made after the checkpoint.
+    fn rollback(&mut self, checkpoint: Checkpoint) {
+        for id in self.definitions.drain(checkpoint.definitions..) {
+            self.defined[id as usize] = false;
+        }
+        for name in self.names.drain(checkpoint.names..) {
+            self.ids.remove(name);
+        }
+        self.defined.truncate(checkpoint.names);
+    }
+}
+
+/// Maps identifiers to dense ids, in the order in which they're first
+/// encountered, regardless of whether that is a reference or definition.
+/// Shared between the parsers created by `reference` and `define`, so that
+/// names may be referred to before they are defined.
+///
+/// Identifiers are recorded as soon as they are parsed, and are not undone
+/// when an enclosing parser fails, such as a branch of `or` or `alt`. Wrap
+/// parsers that may be backtracked out of in `transaction`, so that a failed
+/// branch does not leave spurious references or definitions behind.
+#[derive(Debug, Default)]
+pub struct Interner<'s> {
+    table: RefCell<Table<'s>>,
+}
+
+/// The identifiers of an `Interner`, once all of them have been resolved.
+#[derive(Debug, Clone, Default)]
+pub struct Symbols<'s> {
+    ids: HashMap<&'s [u8], u32>,
+    names: Vec<&'s [u8]>,
+}
+
+impl<'s> Interner<'s> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Interns the identifier parsed by `parser`, returning its id.
+    pub fn reference<P>(&self, parser: P) -> Reference<'_, 's, P>
+    where
+        P: Parser<'s, Output = &'s [u8]>,
+    {
+        Reference {
+            interner: self,
+            parser,
+        }
+    }
+
+    /// Interns the identifier parsed by `parser`, returning its id, it is an
+    /// error for the same identifier to be defined more than once.
+    pub fn define<P>(&self, parser: P) -> Define<'_, 's, P>
+    where
+        P: Parser<'s, Output = &'s [u8]>,
+    {
+        Define {
+            interner: self,
+            parser,
+        }
+    }
+
+    /// Applies `parser`, and undoes all references and definitions it made
+    /// when it fails.
+    pub fn transaction<P: Parser<'s>>(&self, parser: P) -> Transaction<'_, 's, P> {
+        Transaction {
+            interner: self,
+            parser,
+        }
+    }
+
+    pub fn id(&self, name: &[u8]) -> Option<u32> {
+        self.table.borrow().ids.get(name).copied()
+    }
+
+    pub fn name(&self, id: u32) -> &'s [u8] {
+        self.table.borrow().names[id as usize]
+    }
+
+    pub fn len(&self) -> usize {
+        self.table.borrow().names.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Verifies that every identifier that was referenced has been defined.
+    pub fn resolve(self) -> Result<Symbols<'s>, ParseError> {
+        let Table {
+            ids,
+            names,
+            defined,
+            ..
+        } = self.table.into_inner();
+        if let Some(index) = defined.iter().position(|&defined| !defined) {
+            return Err(ParseError::UndefinedIdentifier(identifier(names[index])));
+        }
+        Ok(Symbols { ids, names })
+    }
+}
+
+impl<'s> Symbols<'s> {
+    pub fn id(&self, name: &[u8]) -> Option<u32> {
+        self.ids.get(name).copied()
+    }
+
+    pub fn name(&self, id: u32) -> &'s [u8] {
+        self.names[id as usize]
+    }
+
+    pub fn len(&self) -> usize {
+        self.names.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.names.is_empty()
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct Reference<'i, 's, P> {
+    interner: &'i Interner<'s>,
+    parser: P,
+}
+
+impl<'i, 's, P> Parser<'s> for Reference<'i, 's, P>
+where
+    P: Parser<'s, Output = &'s [u8]>,
+{
+    type Output = u32;
+
+    fn parse(&self, input: &'s [u8]) -> ParseResult<'s, Self::Output> {
+        let (name, remainder) = self.parser.parse(input)?;
+        Ok((self.interner.table.borrow_mut().intern(name), remainder))
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct Define<'i, 's, P> {
+    interner: &'i Interner<'s>,
+    parser: P,
+}
+
+impl<'i, 's, P> Parser<'s> for Define<'i, 's, P>
+where
+    P: Parser<'s, Output = &'s [u8]>,
+{
+    type Output = u32;
+
+    fn parse(&self, input: &'s [u8]) -> ParseResult<'s, Self::Output> {
+        let (name, remainder) = self.parser.parse(input)?;
+        let mut table = self.interner.table.borrow_mut();
+        let id = table.intern(name);
+        if std::mem::replace(&mut table.defined[id as usize], true) {
+            return Err((ParseError::DuplicateIdentifier(identifier(name)), input));
+        }
+        table.definitions.push(id);
+        Ok((id, remainder))
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct Transaction<'i, 's, P> {
+    interner: &'i Interner<'s>,
+    parser: P,
+}
+
+impl<'i, 's, P: Parser<'s>> Parser<'s> for Transaction<'i, 's, P> {
+    type Output = P::Output;
+
+    fn parse(&self, input: &'s [u8]) -> ParseResult<'s, Self::Output> {
+        let checkpoint = self.interner.table.borrow().checkpoint();
+        let result = self.parser.parse(input);
+        if result.is_err() {
+            self.interner.table.borrow_mut().rollback(checkpoint);
+        }
+        result
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn interning() {
+        let interner = Interner::new();
+        let name = take_while((), |_, c| c.is_ascii_alphabetic());
+        let node = interner
+            .define(name)
+            .and(token(b" -> ").then(interner.reference(name).sep_by::<_, Vec<_>>(token(b", "))));
+        let nodes = node.clone().sep_by::<_, Vec<_>>(token(b'\n'));
+
+        assert_eq!(
+            Ok((vec![(0, vec![1, 2]), (1, vec![0]), (2, vec![0])], &b""[..])),
+            nodes.parse(b"a -> b, c\nb -> a\nc -> a")
+        );
+        assert_eq!(Some(2), interner.id(b"c"));
+        assert_eq!(b"b", interner.name(1));
+        assert_eq!(Ok(((3, vec![4]), &b""[..])), node.parse(b"d -> e"));
+        assert_eq!(
+            Err((ParseError::DuplicateIdentifier("a".into()), &b"a -> c"[..])),
+            node.parse(b"a -> c")
+        );
+        assert_eq!(
+            Err(ParseError::UndefinedIdentifier("e".into())),
+            interner.resolve().map(|symbols| symbols.len())
+        );
+    }
+
+    #[test]
+    fn transactions() {
+        let interner = Interner::new();
+        let name = take_while((), |_, c| c.is_ascii_alphabetic());
+        let value = token(b" = ").then(interner.reference(name));
+        let assignment = interner.define(name).and(value).and(token(b';'));
+        let statement = interner
+            .transaction(assignment)
+            .map(|((_, value), _)| value)
+            .or(interner.reference(name));
+        let statements = statement.sep_by::<_, Vec<_>>(token(b'\n'));
+
+        // The assignment to `b` lacks a `;`, so it is rolled back, and `b` is
+        // parsed as a reference instead. `x` is forgotten.
+        assert_eq!(
+            Ok((vec![1, 1], &b" = x"[..])),
+            statements.parse(b"a = b;\nb = x")
+        );
+        assert_eq!(None, interner.id(b"x"));
+        assert_eq!(
+            Err(ParseError::UndefinedIdentifier("b".into())),
+            interner.resolve().map(|symbols| symbols.len())
+        );
+    }
+}
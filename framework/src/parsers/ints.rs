@@ -0,0 +1,170 @@
+use super::numbers::{IsParsableInteger, NumberParser};
+use super::util::split_line;
+use super::*;
+use std::marker::PhantomData;
+
+/// Iterator over all integers in a string, ignoring any other bytes, created
+/// by `AStrExt::ints`. Yields an error for each integer that does not fit in
+/// `T`, and continues after it, so use `collect::<Result<Vec<_>, _>>()` to
+/// stop at the first error.
+#[derive(Debug, Clone)]
+pub struct Ints<'s, T> {
+    input: &'s [u8],
+    index: usize,
+    _t: PhantomData<T>,
+}
+
+impl<'s, T: IsParsableInteger> Ints<'s, T> {
+    pub fn new(input: &'s [u8]) -> Self {
+        Ints {
+            input,
+            index: 0,
+            _t: PhantomData,
+        }
+    }
+}
+
+impl<'s, T: IsParsableInteger> Ints<'s, T>
+where
+    NumberParser<T>: Parser<'s, Output = T>,
+{
+    /// Finds the start of the next integer. For signed integers, a `-` is
+    /// considered part of the integer, only when it is directly followed by a
+    /// digit, and not directly preceded by one, so that ranges such as `2-4`
+    /// are two positive numbers.
+    fn find_next(&self) -> Option<usize> {
+        let input = self.input;
+        (self.index..input.len()).find(|&i| match input[i] {
+            b'0'..=b'9' => true,
+            b'-' if T::SIGNED => {
+                input.get(i + 1).is_some_and(u8::is_ascii_digit)
+                    && (i == 0 || !input[i - 1].is_ascii_digit())
+            }
+            _ => false,
+        })
+    }
+
+    /// Parses the next integer, returning an error if it does not fit in `T`.
+    /// After an error, the next call continues after the failed integer.
+    pub fn try_next(&mut self) -> Option<ParseResult<'s, T>> {
+        let start = self.find_next()?;
+        let result = number::<T>().parse(&self.input[start..]);
+        self.index = match result {
+            Ok((_, remainder)) => self.input.len() - remainder.len(),
+            Err(_) => {
+                let digits = self.input[start + 1..]
+                    .iter()
+                    .take_while(|c| c.is_ascii_digit())
+                    .count();
+                start + 1 + digits
+            }
+        };
+        Some(result)
+    }
+}
+
+impl<'s, T: IsParsableInteger> Iterator for Ints<'s, T>
+where
+    NumberParser<T>: Parser<'s, Output = T>,
+{
+    type Item = Result<T, ParseError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        Some(self.try_next()?.map(|(value, _)| value).map_err(|(e, _)| e))
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct IntsParser<T>(PhantomData<T>);
+
+#[derive(Debug, Clone, Copy)]
+pub struct IntsArrayParser<T, const N: usize>(PhantomData<T>);
+
+/// Parses all integers on the current line, skipping over any other bytes.
+/// Fails when the line contains no integers. See `Ints` for how a `-` is
+/// interpreted.
+pub fn ints<T: IsParsableInteger>() -> IntsParser<T> {
+    IntsParser(PhantomData)
+}
+
+impl<T: IsParsableInteger> IntsParser<T> {
+    /// Parses exactly N integers on the current line into an array, failing
+    /// when there are fewer or more.
+    pub fn collect_array<const N: usize>(self) -> IntsArrayParser<T, N> {
+        IntsArrayParser(PhantomData)
+    }
+}
+
+impl<'s, T: IsParsableInteger + 's> Parser<'s> for IntsParser<T>
+where
+    NumberParser<T>: Parser<'s, Output = T>,
+{
+    type Output = Vec<T>;
+
+    fn parse(&self, input: &'s [u8]) -> ParseResult<'s, Self::Output> {
+        let (line, remainder) = split_line(input);
+        let mut ints = Ints::new(line);
+        let mut values = Vec::new();
+        while let Some(result) = ints.try_next() {
+            values.push(result?.0);
+        }
+        if values.is_empty() {
+            return Err((ParseError::ExpectedDigit, input));
+        }
+        Ok((values, remainder))
+    }
+}
+
+impl<'s, T: IsParsableInteger + 's, const N: usize> Parser<'s> for IntsArrayParser<T, N>
+where
+    NumberParser<T>: Parser<'s, Output = T>,
+{
+    type Output = [T; N];
+
+    fn parse(&self, input: &'s [u8]) -> ParseResult<'s, Self::Output> {
+        let (line, remainder) = split_line(input);
+        let mut ints = Ints::new(line);
+        let mut count = 0;
+        let array = crate::util::init_array(|_| match ints.try_next() {
+            Some(Ok((value, _))) => {
+                count += 1;
+                Ok(value)
+            }
+            Some(Err(e)) => Err(e),
+            None => Err((ParseError::CountMismatch(N, count), input)),
+        })?;
+        while let Some(result) = ints.try_next() {
+            result?;
+            count += 1;
+        }
+        if count != N {
+            return Err((ParseError::CountMismatch(N, count), input));
+        }
+        Ok((array, remainder))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    #[rustfmt::skip]
+    fn ints_on_line() {
+        assert_eq!( Ok((vec![2, 4, 6, 8],               &b"\n1"[..])), ints::<u8>().parse(b"2-4,6-8\n1"));
+        assert_eq!( Ok((vec![2, -4, -5],                &b""   [..])), ints::<i32>().parse(b"x=2, y=-4 - -5"));
+        assert_eq!(Err((ParseError::ExpectedDigit,      &b"-\n"[..])), ints::<i32>().parse(b"-\n"));
+        assert_eq!(Err((ParseError::Overflow,           &b"256"[..])), ints::<u8>().parse(b"a 256"));
+        assert_eq!( Ok(([1, 2, 3],                      &b""   [..])), ints::<u8>().collect_array::<3>().parse(b"move 1 from 2 to 3"));
+        assert_eq!(Err((ParseError::CountMismatch(3, 2), &b"1 2"[..])), ints::<u8>().collect_array::<3>().parse(b"1 2"));
+        assert_eq!(Err((ParseError::CountMismatch(1, 2), &b"1 2"[..])), ints::<u8>().collect_array::<1>().parse(b"1 2"));
+        assert_eq!( Ok((vec![5],                        &b""   [..])), ints::<u32>().parse(b"x -5"));
+        assert_eq!(Ok(vec![1, -2, 3]), b"1\n-2\n+3".ints::<i64>().collect::<Result<Vec<_>, _>>());
+        assert_eq!(vec![Ok(1), Err(ParseError::Overflow), Ok(2)], b"1 256 2".ints::<u8>().collect::<Vec<_>>());
+        let mut ints = b"1 256 2".ints::<u8>();
+        assert_eq!(Some( Ok((1,                    &b" 256 2"[..]))), ints.try_next());
+        assert_eq!(Some(Err((ParseError::Overflow, &b"256 2" [..]))), ints.try_next());
+        assert_eq!(Some( Ok((2,                    &b""      [..]))), ints.try_next());
+        assert_eq!(None, ints.try_next());
+    }
+}
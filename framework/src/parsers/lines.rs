@@ -0,0 +1,139 @@
+use super::util::parse_fully;
+use super::*;
+use rayon::prelude::*;
+
+/// Splits the input into lines, ignoring a trailing newline.
+fn split_lines(input: &[u8]) -> impl Iterator<Item = &[u8]> {
+    let content = input.strip_suffix(b"\n").unwrap_or(input);
+    content.split(|&c| c == b'\n')
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct LinesPar<P>(P);
+
+#[derive(Debug, Clone, Copy)]
+pub struct LinesRecover<P>(P);
+
+/// Splits the input into lines, and applies the parser to each line in
+/// parallel, returning the outputs in the order of the lines. Each line must
+/// be fully consumed, a trailing newline is allowed. Errors are wrapped in
+/// `ParseError::AtLine`, with the 1-based number of the first line that fails.
+///
+/// Only worth it for large inputs, for smaller inputs the overhead of
+/// distributing the work outweighs the parsing itself.
+pub fn lines_par<'s, P>(parser: P) -> LinesPar<P>
+where
+    P: Parser<'s> + Sync,
+    P::Output: Send,
+{
+    LinesPar(parser)
+}
+
+impl<'s, P> Parser<'s> for LinesPar<P>
+where
+    P: Parser<'s> + Sync,
+    P::Output: Send,
+{
+    type Output = Vec<P::Output>;
+
+    fn parse(&self, input: &'s [u8]) -> ParseResult<'s, Self::Output> {
+        if input.is_empty() {
+            return Err((ParseError::EmptyInput, input));
+        }
+        let lines: Vec<&'s [u8]> = split_lines(input).collect();
+        let results: Vec<_> = lines
+            .par_iter()
+            .map(|line| {
+                parse_fully(
+                    &self.0,
+                    line,
+                    EndOfInput::Strict,
+                    ParseError::LineNotConsumed,
+                )
+            })
+            .collect();
+
+        let mut outputs = Vec::with_capacity(results.len());
+        for (index, result) in results.into_iter().enumerate() {
+            match result {
+                Ok(output) => outputs.push(output),
+                Err((e, remainder)) => {
+                    return Err((ParseError::AtLine(index + 1, Box::new(e)), remainder))
+                }
+            }
+        }
+        Ok((outputs, &input[input.len()..]))
+    }
+}
+
+/// Splits the input into lines, and applies the parser to each line, which it
+/// must consume fully. Unlike `sep_by`, it does not stop at the first line that
+/// fails, but continues with the next line. When any lines fail, the errors of
+/// all of them are returned in `ParseError::Multiple`, each wrapped in
+/// `ParseError::AtLine`.
+pub fn lines_recover<'s, P: Parser<'s>>(parser: P) -> LinesRecover<P> {
+    LinesRecover(parser)
+}
+
+impl<'s, P: Parser<'s>> Parser<'s> for LinesRecover<P> {
+    type Output = Vec<P::Output>;
+
+    fn parse(&self, input: &'s [u8]) -> ParseResult<'s, Self::Output> {
+        if input.is_empty() {
+            return Err((ParseError::EmptyInput, input));
+        }
+        let mut outputs = Vec::new();
+        let mut errors = Vec::new();
+        let mut first_error_remainder = None;
+        for (index, line) in split_lines(input).enumerate() {
+            match parse_fully(
+                &self.0,
+                line,
+                EndOfInput::Strict,
+                ParseError::LineNotConsumed,
+            ) {
+                Ok(output) => outputs.push(output),
+                Err((e, remainder)) => {
+                    first_error_remainder.get_or_insert(remainder);
+                    errors.push(ParseError::AtLine(index + 1, Box::new(e)));
+                }
+            }
+        }
+        match first_error_remainder {
+            None => Ok((outputs, &input[input.len()..])),
+            Some(remainder) => Err((ParseError::Multiple(errors), remainder)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    #[rustfmt::skip]
+    fn parallel_lines() {
+        let p = lines_par(number::<u32>().and(token(b',').then(number::<u32>())));
+        let input = (0..1000).map(|i| format!("{i},{}\n", i * 2)).collect::<String>();
+        let expected = (0..1000).map(|i| (i, i * 2)).collect::<Vec<_>>();
+        assert_eq!(Ok((expected, &b""[..])), p.parse(input.as_bytes()));
+        assert_eq!(Err((ParseError::AtLine(2, Box::new(ParseError::LineNotConsumed)), &b"x"[..])), p.parse(b"1,2\n3,4x\n5,x"));
+        assert_eq!(Err((ParseError::AtLine(3, Box::new(ParseError::ExpectedDigit)),   &b"x"[..])), p.parse(b"1,2\n3,4\n5,x"));
+    }
+
+    #[test]
+    fn recovering_lines() {
+        let p = lines_recover(number::<u8>().and(token(b',').then(number::<u8>())));
+        assert_eq!(Ok((vec![(1, 2), (3, 4)], &b""[..])), p.parse(b"1,2\n3,4\n"));
+        assert_eq!(
+            Err((
+                ParseError::Multiple(vec![
+                    ParseError::AtLine(2, Box::new(ParseError::Overflow)),
+                    ParseError::AtLine(4, Box::new(ParseError::LineNotConsumed)),
+                ]),
+                &b"300,1"[..]
+            )),
+            p.parse(b"1,2\n300,1\n5,6\n7,8 ")
+        );
+    }
+}
@@ -1,32 +1,89 @@
+mod columns;
 mod combi;
 mod common;
+mod direction;
 pub mod error;
+pub mod format;
 mod grid;
+mod intern;
+mod ints;
+mod lines;
 mod multi;
 pub mod numbers;
+pub mod pat;
+mod print;
+mod record;
+mod recursive;
+mod sections;
+mod stream;
 mod util;
+mod whitespace;
 
-pub use combi::ParserCombiExt;
+pub use columns::columns;
+pub use combi::{alt, delimited, preceded, separated_pair, ParserCombiExt};
 pub use common::{any, digit, pattern, token};
+pub use direction::{arrows, compass, udlr, DirectionParser, YAxis};
 pub use error::{ParseError, ParseResult};
-pub use grid::grid;
+pub use format::parse_format;
+pub use grid::{grid, grid3};
+pub use intern::{Interner, Symbols};
+pub use ints::{ints, Ints};
+pub use lines::{lines_par, lines_recover};
 pub use multi::{take_while, ParserMultiExt};
-pub use numbers::number;
+pub use numbers::{bin, hex, number, oct};
+pub use pat::pat;
+pub use print::{assert_round_trip, Printer};
+pub use record::{field, record};
+pub use recursive::recursive;
+pub use sections::{sections, sections_tuple, Sections};
+pub use stream::{stream_lines, StreamLines};
 pub use util::AStrExt;
+pub use whitespace::{lexeme, newline, spaces, ws};
 
 pub trait Parser<'s> {
     type Output: 's;
     fn parse(&self, input: &'s [u8]) -> ParseResult<'s, Self::Output>;
 }
 
+/// Which input may remain after a parser is executed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum EndOfInput {
+    /// The input must be consumed fully.
+    Strict,
+    /// A single newline may remain, this is the default.
+    #[default]
+    TrailingNewline,
+    /// Any number of blank lines may remain, lines may end in `\r\n`.
+    TrailingBlankLines,
+    /// Any ASCII whitespace may remain.
+    TrailingWhitespace,
+}
+
+impl EndOfInput {
+    pub fn allows(self, remainder: &[u8]) -> bool {
+        match self {
+            EndOfInput::Strict => remainder.is_empty(),
+            EndOfInput::TrailingNewline => matches!(remainder, [] | [b'\n']),
+            EndOfInput::TrailingBlankLines => remainder
+                .split(|&c| c == b'\n')
+                .all(|line| matches!(line, [] | [b'\r'])),
+            EndOfInput::TrailingWhitespace => remainder.iter().all(u8::is_ascii_whitespace),
+        }
+    }
+}
+
 pub trait Execute<'s, T> {
-    fn execute(&self, input: &'s [u8]) -> crate::result::Result<T>;
+    fn execute(&self, input: &'s [u8]) -> crate::result::Result<T> {
+        self.execute_with(input, EndOfInput::default())
+    }
+
+    fn execute_with(&self, input: &'s [u8], end: EndOfInput) -> crate::result::Result<T>;
 }
 
 impl<'s, P: Parser<'s, Output = T>, T> Execute<'s, T> for P {
-    fn execute(&self, input: &'s [u8]) -> crate::result::Result<T> {
+    fn execute_with(&self, input: &'s [u8], end: EndOfInput) -> crate::result::Result<T> {
         Err(match self.parse(input) {
-            Ok((x, [] | [b'\n'])) => return Ok(x),
+            Ok((x, remainder)) if end.allows(remainder) => return Ok(x),
             Ok((_, remainder)) => {
                 ParseError::InputNotConsumed(String::from_utf8_lossy(remainder).into_owned())
             }
@@ -1,6 +1,7 @@
 use std::{fmt::Debug, marker::PhantomData};
 
 use super::*;
+use crate::astr::AString;
 
 pub trait ParserMultiExt<'s>: Sized + Parser<'s> {
     /// Repeatedly applies the parser, interspersing applications of `separator`.
@@ -164,6 +165,28 @@ where
     }
 }
 
+impl<T, P, S, C> Printer<C> for SepBy<P, S, C>
+where
+    P: Printer<T>,
+    S: Printer<()>,
+    for<'c> &'c C: IntoIterator<Item = &'c T>,
+{
+    fn print(&self, value: &C, output: &mut AString) {
+        for (index, element) in value.into_iter().enumerate() {
+            if index != 0 {
+                self.separator.print(&(), output);
+            }
+            self.parser.print(element, output);
+        }
+    }
+
+    fn can_print(&self, value: &C) -> bool {
+        value
+            .into_iter()
+            .all(|element| self.parser.can_print(element))
+    }
+}
+
 impl<'s, P, A, F> Parser<'s> for Fold<P, A, F>
 where
     P: Parser<'s>,
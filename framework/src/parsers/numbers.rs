@@ -1,29 +1,106 @@
-use std::marker::PhantomData;
+use num::bigint::{BigInt, BigUint, Sign};
+use std::{fmt::Write, marker::PhantomData};
 
+use super::print::{fits_digits, print_digits};
 use super::*;
+use crate::astr::AString;
+
+/// Returns the value of a digit in the given radix, if it is a valid digit.
+#[inline]
+fn digit_value(c: u8, radix: u32) -> Option<u8> {
+    if radix == 10 {
+        let digit = c.wrapping_sub(b'0');
+        return (digit < 10).then_some(digit);
+    }
+    (c as char).to_digit(radix).map(|d| d as u8)
+}
+
+/// Returns the length of the prefix of `input` that is a valid digit, given
+/// the radix, when separators are enabled, a single `_` is allowed in between
+/// any two digits.
+#[inline]
+fn digits_len(input: &[u8], radix: u32, separators: bool) -> usize {
+    let mut index = 0;
+    while let Some(&c) = input.get(index) {
+        if digit_value(c, radix).is_none()
+            && !(separators
+                && c == b'_'
+                && index != 0
+                && input
+                    .get(index + 1)
+                    .is_some_and(|&c| digit_value(c, radix).is_some()))
+        {
+            break;
+        }
+        index += 1;
+    }
+    index
+}
+
+/// Returns the length of the prefix of `input` that holds exactly `n` digits,
+/// given the radix, when separators are enabled, a single `_` is allowed in
+/// between any two digits.
+#[inline]
+fn fixed_digits_len(
+    input: &[u8],
+    n: usize,
+    radix: u32,
+    separators: bool,
+) -> Result<usize, ParseError> {
+    let mut index = 0;
+    for i in 0..n {
+        if separators && i != 0 && input.get(index) == Some(&b'_') {
+            index += 1;
+        }
+        match input.get(index) {
+            Some(&c) if digit_value(c, radix).is_some() => index += 1,
+            None if index == 0 => return Err(ParseError::EmptyInput),
+            _ => return Err(ParseError::ExpectedDigit),
+        }
+    }
+    Ok(index)
+}
 
 macro_rules! impl_uint_parsing {
     ($kind:tt) => {
         impl $crate::parsers::numbers::IsParsableNumber for $kind {}
-        impl<'s> $crate::parsers::Parser<'s> for NumberParser<$kind> {
+        impl $crate::parsers::numbers::IsParsableInteger for $kind {}
+        impl<'s, const RADIX: u32> $crate::parsers::Parser<'s> for NumberParser<$kind, RADIX> {
             type Output = $kind;
 
             fn parse(&self, input: &'s [u8]) -> ParseResult<'s, Self::Output> {
                 let first_char = *input.first().ok_or((ParseError::EmptyInput, input))?;
-                if !matches!(first_char, b'0'..=b'9') {
-                    return Err((ParseError::ExpectedDigit, input));
-                }
+                let mut x = digit_value(first_char, RADIX)
+                    .ok_or((ParseError::ExpectedDigit, input))? as $kind;
 
                 let mut remainder = &input[1..];
-
-                let mut x = (first_char as $kind) - ('0' as $kind);
+                if !self.separators {
+                    while let Some(d) = remainder.first().and_then(|&c| digit_value(c, RADIX)) {
+                        x = x
+                            .checked_mul(RADIX as $kind)
+                            .and_then(|x| x.checked_add(d as $kind))
+                            .ok_or((ParseError::Overflow, input))?;
+                        remainder = &remainder[1..];
+                    }
+                    return Ok((x, remainder));
+                }
                 loop {
                     let next_digit = match remainder.first() {
-                        Some(&c @ b'0'..=b'9') => (c as $kind) - ('0' as $kind),
-                        _ => break,
+                        Some(&b'_') if self.separators => match remainder.get(1) {
+                            Some(&c) if digit_value(c, RADIX).is_some() => {
+                                remainder = &remainder[1..];
+                                continue;
+                            }
+                            _ => break,
+                        },
+                        Some(&c) => match digit_value(c, RADIX) {
+                            Some(d) => d as $kind,
+                            None => break,
+                        },
+                        None => break,
                     };
                     x = x
-                        .checked_mul(10)
+                        .checked_mul(RADIX as $kind)
                         .and_then(|x| x.checked_add(next_digit))
                         .ok_or((ParseError::Overflow, input))?;
                     remainder = &remainder[1..];
@@ -32,6 +109,44 @@ macro_rules! impl_uint_parsing {
                 Ok((x, remainder))
             }
         }
+
+        impl<'s, const RADIX: u32, const N: usize> $crate::parsers::Parser<'s>
+            for FixedNumberParser<$kind, RADIX, N>
+        {
+            type Output = $kind;
+
+            fn parse(&self, input: &'s [u8]) -> ParseResult<'s, Self::Output> {
+                let length =
+                    fixed_digits_len(input, N, RADIX, self.separators).map_err(|e| (e, input))?;
+                let mut x: $kind = 0;
+                for &c in input[..length].iter().filter(|&&c| c != b'_') {
+                    x = x
+                        .checked_mul(RADIX as $kind)
+                        .and_then(|x| x.checked_add(digit_value(c, RADIX).unwrap() as $kind))
+                        .ok_or((ParseError::Overflow, input))?;
+                }
+                Ok((x, &input[length..]))
+            }
+        }
+
+        impl<const RADIX: u32> Printer<$kind> for NumberParser<$kind, RADIX> {
+            fn print(&self, value: &$kind, output: &mut AString) {
+                print_digits(*value as u128, RADIX, 1, output);
+            }
+        }
+
+        impl<const RADIX: u32, const N: usize> Printer<$kind>
+            for FixedNumberParser<$kind, RADIX, N>
+        {
+            fn print(&self, value: &$kind, output: &mut AString) {
+                debug_assert!(self.can_print(value));
+                print_digits(*value as u128, RADIX, N, output);
+            }
+
+            fn can_print(&self, value: &$kind) -> bool {
+                fits_digits(*value as u128, RADIX, N)
+            }
+        }
     };
 }
 
@@ -40,7 +155,10 @@ macro_rules! impl_sint_parsing {
         /// Parses an integer. Allows an optional + or - at the start to
         /// indicate a sign.
         impl $crate::parsers::numbers::IsParsableNumber for $kind {}
-        impl<'s> $crate::parsers::Parser<'s> for NumberParser<$kind> {
+        impl $crate::parsers::numbers::IsParsableInteger for $kind {
+            const SIGNED: bool = true;
+        }
+        impl<'s, const RADIX: u32> $crate::parsers::Parser<'s> for NumberParser<$kind, RADIX> {
             type Output = $kind;
 
             fn parse(&self, input: &'s [u8]) -> ParseResult<'s, Self::Output> {
@@ -49,8 +167,11 @@ macro_rules! impl_sint_parsing {
                     Some(&b'+') => (false, &input[1..]),
                     _ => (false, input),
                 };
-                let (number, remainder) =
-                    $crate::parsers::number::<$unsigned>().parse(remainder)?;
+                let unsigned = NumberParser::<$unsigned, RADIX> {
+                    separators: self.separators,
+                    _t: PhantomData,
+                };
+                let (number, remainder) = unsigned.parse(remainder)?;
                 const MAX: $unsigned = $kind::MAX as $unsigned;
                 const MAX_PLUS_ONE: $unsigned = MAX + 1;
                 let number = match (number, is_negative) {
@@ -62,9 +183,126 @@ macro_rules! impl_sint_parsing {
                 Ok((number, remainder))
             }
         }
+
+        /// Parses exactly N digits, without a sign.
+        impl<'s, const RADIX: u32, const N: usize> $crate::parsers::Parser<'s>
+            for FixedNumberParser<$kind, RADIX, N>
+        {
+            type Output = $kind;
+
+            fn parse(&self, input: &'s [u8]) -> ParseResult<'s, Self::Output> {
+                let unsigned = FixedNumberParser::<$unsigned, RADIX, N> {
+                    separators: self.separators,
+                    _t: PhantomData,
+                };
+                let (number, remainder) = unsigned.parse(input)?;
+                if number > $kind::MAX as $unsigned {
+                    return Err((ParseError::Overflow, input));
+                }
+                Ok((number as $kind, remainder))
+            }
+        }
+
+        impl<const RADIX: u32> Printer<$kind> for NumberParser<$kind, RADIX> {
+            fn print(&self, value: &$kind, output: &mut AString) {
+                if *value < 0 {
+                    output.push(b'-');
+                }
+                print_digits(value.unsigned_abs() as u128, RADIX, 1, output);
+            }
+        }
+
+        impl<const RADIX: u32, const N: usize> Printer<$kind>
+            for FixedNumberParser<$kind, RADIX, N>
+        {
+            fn print(&self, value: &$kind, output: &mut AString) {
+                debug_assert!(self.can_print(value));
+                print_digits(*value as u128, RADIX, N, output);
+            }
+
+            fn can_print(&self, value: &$kind) -> bool {
+                *value >= 0 && fits_digits(*value as u128, RADIX, N)
+            }
+        }
     };
 }
 
+macro_rules! impl_float_parsing {
+    ($kind:tt) => {
+        /// Parses a decimal floating point number. Allows an optional + or -
+        /// at the start to indicate a sign, a fractional part, and an exponent.
+        impl $crate::parsers::numbers::IsParsableNumber for $kind {}
+        impl<'s> $crate::parsers::Parser<'s> for NumberParser<$kind> {
+            type Output = $kind;
+
+            fn parse(&self, input: &'s [u8]) -> ParseResult<'s, Self::Output> {
+                let length = float_len(input, self.separators).map_err(|e| (e, input))?;
+                let text = &input[..length];
+                let value = if self.separators && text.contains(&b'_') {
+                    let text: String = text
+                        .iter()
+                        .filter(|&&c| c != b'_')
+                        .map(|&c| c as char)
+                        .collect();
+                    text.parse::<$kind>()
+                } else {
+                    // Only contains ASCII, as validated by float_len
+                    std::str::from_utf8(text).unwrap().parse::<$kind>()
+                };
+                match value {
+                    Ok(value) => Ok((value, &input[length..])),
+                    Err(_) => Err((ParseError::ExpectedDigit, input)),
+                }
+            }
+        }
+
+        impl Printer<$kind> for NumberParser<$kind> {
+            fn print(&self, value: &$kind, output: &mut AString) {
+                let mut text = String::new();
+                write!(text, "{value}").unwrap();
+                output.extend_from_slice(text.as_bytes());
+            }
+
+            /// The parser does not accept `NaN` or infinities.
+            fn can_print(&self, value: &$kind) -> bool {
+                value.is_finite()
+            }
+        }
+    };
+}
+
+/// Returns the length of the prefix of `input` that forms a floating point
+/// number. A `.` is only included when followed by a digit, and an exponent
+/// is only included when it has digits.
+fn float_len(input: &[u8], separators: bool) -> Result<usize, ParseError> {
+    if input.is_empty() {
+        return Err(ParseError::EmptyInput);
+    }
+    let mut index = matches!(input[0], b'+' | b'-') as usize;
+    let integer_len = digits_len(&input[index..], 10, separators);
+    index += integer_len;
+    let mut has_digits = integer_len != 0;
+    if input.get(index) == Some(&b'.') {
+        let fraction_len = digits_len(&input[index + 1..], 10, separators);
+        if fraction_len != 0 {
+            index += fraction_len + 1;
+            has_digits = true;
+        }
+    }
+    if !has_digits {
+        return Err(ParseError::ExpectedDigit);
+    }
+    if let Some(b'e' | b'E') = input.get(index) {
+        let sign_len = matches!(input.get(index + 1), Some(b'+' | b'-')) as usize;
+        let exponent_start = index + 1 + sign_len;
+        let exponent_len = digits_len(&input[exponent_start..], 10, separators);
+        if exponent_len != 0 {
+            index = exponent_start + exponent_len;
+        }
+    }
+    Ok(index)
+}
+
 impl_uint_parsing!(u8);
 impl_uint_parsing!(u16);
 impl_uint_parsing!(u32);
@@ -79,10 +317,196 @@ impl_sint_parsing!(i64, u64);
 impl_sint_parsing!(i128, u128);
 impl_sint_parsing!(isize, usize);
 
+impl_float_parsing!(f32);
+impl_float_parsing!(f64);
+
+/// Parses the digits at the start of `input` into a `BigUint`.
+fn parse_big_uint<'s>(input: &'s [u8], radix: u32, separators: bool) -> ParseResult<'s, BigUint> {
+    let length = digits_len(input, radix, separators);
+    if length == 0 {
+        let e = match input.is_empty() {
+            true => ParseError::EmptyInput,
+            false => ParseError::ExpectedDigit,
+        };
+        return Err((e, input));
+    }
+    let digits: Vec<u8> = input[..length]
+        .iter()
+        .filter(|&&c| c != b'_')
+        .map(|&c| digit_value(c, radix).unwrap())
+        .collect();
+    let value = BigUint::from_radix_be(&digits, radix).unwrap();
+    Ok((value, &input[length..]))
+}
+
+/// Parses exactly N digits into a `BigUint`.
+fn parse_fixed_big_uint<const N: usize>(
+    input: &[u8],
+    radix: u32,
+    separators: bool,
+) -> ParseResult<'_, BigUint> {
+    let length = fixed_digits_len(input, N, radix, separators).map_err(|e| (e, input))?;
+    parse_big_uint(&input[..length], radix, separators).map(|(value, _)| (value, &input[length..]))
+}
+
+impl IsParsableNumber for BigUint {}
+impl IsParsableInteger for BigUint {}
+impl<'s, const RADIX: u32> Parser<'s> for NumberParser<BigUint, RADIX> {
+    type Output = BigUint;
+
+    fn parse(&self, input: &'s [u8]) -> ParseResult<'s, Self::Output> {
+        parse_big_uint(input, RADIX, self.separators)
+    }
+}
+
+impl<'s, const RADIX: u32, const N: usize> Parser<'s> for FixedNumberParser<BigUint, RADIX, N> {
+    type Output = BigUint;
+
+    fn parse(&self, input: &'s [u8]) -> ParseResult<'s, Self::Output> {
+        parse_fixed_big_uint::<N>(input, RADIX, self.separators)
+    }
+}
+
+impl<const RADIX: u32> Printer<BigUint> for NumberParser<BigUint, RADIX> {
+    fn print(&self, value: &BigUint, output: &mut AString) {
+        output.extend_from_slice(value.to_str_radix(RADIX).as_bytes());
+    }
+}
+
+impl<const RADIX: u32, const N: usize> Printer<BigUint> for FixedNumberParser<BigUint, RADIX, N> {
+    fn print(&self, value: &BigUint, output: &mut AString) {
+        let digits = value.to_str_radix(RADIX);
+        debug_assert!(digits.len() <= N);
+        output.resize(output.len() + N.saturating_sub(digits.len()), b'0');
+        output.extend_from_slice(digits.as_bytes());
+    }
+
+    fn can_print(&self, value: &BigUint) -> bool {
+        value.to_str_radix(RADIX).len() <= N
+    }
+}
+
+/// Parses an integer. Allows an optional + or - at the start to indicate a
+/// sign.
+impl IsParsableNumber for BigInt {}
+impl IsParsableInteger for BigInt {
+    const SIGNED: bool = true;
+}
+impl<'s, const RADIX: u32> Parser<'s> for NumberParser<BigInt, RADIX> {
+    type Output = BigInt;
+
+    fn parse(&self, input: &'s [u8]) -> ParseResult<'s, Self::Output> {
+        let (sign, remainder) = match input.first() {
+            Some(&b'-') => (Sign::Minus, &input[1..]),
+            Some(&b'+') => (Sign::Plus, &input[1..]),
+            _ => (Sign::Plus, input),
+        };
+        let (number, remainder) = parse_big_uint(remainder, RADIX, self.separators)?;
+        Ok((BigInt::from_biguint(sign, number), remainder))
+    }
+}
+
+/// Parses exactly N digits, without a sign.
+impl<'s, const RADIX: u32, const N: usize> Parser<'s> for FixedNumberParser<BigInt, RADIX, N> {
+    type Output = BigInt;
+
+    fn parse(&self, input: &'s [u8]) -> ParseResult<'s, Self::Output> {
+        let (number, remainder) = parse_fixed_big_uint::<N>(input, RADIX, self.separators)?;
+        Ok((number.into(), remainder))
+    }
+}
+
+impl<const RADIX: u32> Printer<BigInt> for NumberParser<BigInt, RADIX> {
+    fn print(&self, value: &BigInt, output: &mut AString) {
+        output.extend_from_slice(value.to_str_radix(RADIX).as_bytes());
+    }
+}
+
+impl<const RADIX: u32, const N: usize> Printer<BigInt> for FixedNumberParser<BigInt, RADIX, N> {
+    fn print(&self, value: &BigInt, output: &mut AString) {
+        debug_assert!(value.sign() != Sign::Minus);
+        FixedNumberParser::<BigUint, RADIX, N>::new().print(value.magnitude(), output);
+    }
+
+    fn can_print(&self, value: &BigInt) -> bool {
+        value.sign() != Sign::Minus
+            && FixedNumberParser::<BigUint, RADIX, N>::new().can_print(value.magnitude())
+    }
+}
+
 pub trait IsParsableNumber {}
+pub trait IsParsableInteger: IsParsableNumber {
+    /// Whether the integer can be negative, so that a leading `-` is part of
+    /// the number rather than a separate byte.
+    const SIGNED: bool = false;
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct NumberParser<T: IsParsableNumber, const RADIX: u32 = 10> {
+    separators: bool,
+    _t: PhantomData<T>,
+}
+
 #[derive(Debug, Clone, Copy)]
-pub struct NumberParser<T: IsParsableNumber>(PhantomData<T>);
+pub struct FixedNumberParser<T: IsParsableInteger, const RADIX: u32, const N: usize> {
+    separators: bool,
+    _t: PhantomData<T>,
+}
+
+impl<T: IsParsableInteger, const RADIX: u32, const N: usize> FixedNumberParser<T, RADIX, N> {
+    const fn new() -> Self {
+        FixedNumberParser {
+            separators: false,
+            _t: PhantomData,
+        }
+    }
+}
+
+impl<T: IsParsableNumber, const RADIX: u32> NumberParser<T, RADIX> {
+    /// Allows a single `_` in between any two digits, e.g. `1_000_000`.
+    pub const fn separated(self) -> Self {
+        NumberParser {
+            separators: true,
+            _t: PhantomData,
+        }
+    }
+}
 
+impl<T: IsParsableInteger, const RADIX: u32> NumberParser<T, RADIX> {
+    /// Parses exactly N digits, no more and no less. Signs are not allowed,
+    /// separators are only allowed when `separated` was used, and are not
+    /// counted as digits.
+    pub const fn fixed<const N: usize>(self) -> FixedNumberParser<T, RADIX, N> {
+        FixedNumberParser {
+            separators: self.separators,
+            _t: PhantomData,
+        }
+    }
+}
+
+const fn radix<T: IsParsableNumber, const RADIX: u32>() -> NumberParser<T, RADIX> {
+    NumberParser {
+        separators: false,
+        _t: PhantomData,
+    }
+}
+
+/// Parses a decimal number.
 pub const fn number<T: IsParsableNumber>() -> NumberParser<T> {
-    NumberParser(PhantomData)
+    radix()
+}
+
+/// Parses a hexadecimal integer, without `0x` prefix, case insensitive.
+pub const fn hex<T: IsParsableInteger>() -> NumberParser<T, 16> {
+    radix()
+}
+
+/// Parses an octal integer, without `0o` prefix.
+pub const fn oct<T: IsParsableInteger>() -> NumberParser<T, 8> {
+    radix()
+}
+
+/// Parses a binary integer, without `0b` prefix.
+pub const fn bin<T: IsParsableInteger>() -> NumberParser<T, 2> {
+    radix()
 }
@@ -0,0 +1,49 @@
+#[doc(hidden)]
+pub use macros::pat as __pat;
+
+/// Creates a parser from a pattern that is checked at compile time, the output
+/// is the matched slice of the input. A pattern is a sequence of byte classes,
+/// each optionally followed by a quantifier.
+///
+/// Supported classes:
+/// - `a`: a literal byte, other than one of `.[]{}()|?*+\`.
+/// - `\.`, `\[`, etc: one of `\[]-^.?*+{}()|`, literally. Escaping any other
+///   byte is an error.
+/// - `\d`, `\w`, `\s`: an ASCII digit, word byte or whitespace byte.
+/// - `\n`, `\t`: a newline or tab.
+/// - `.`: any byte except a newline.
+/// - `[a-z_]`, `[^,\n]`: any byte in, or not in, the listed bytes and ranges.
+///
+/// Supported quantifiers: `?`, `*`, `+`, `{n}`, `{n,}` and `{n,m}`.
+///
+/// Repetitions are greedy and never backtrack, so `[a-z]+z` can never match.
+///
+/// ```
+/// use advent_of_code_framework::parsers::*;
+///
+/// let code = pat!(r"[A-Z]{2,4}-\d+");
+/// assert_eq!(Ok((&b"AB-12"[..], &b" x"[..])), code.parse(b"AB-12 x"));
+/// ```
+pub macro pat($pattern:literal) {
+    $crate::parsers::pat::__pat!($crate, $pattern)
+}
+
+#[cfg(test)]
+mod test {
+    use super::super::*;
+
+    #[test]
+    #[rustfmt::skip]
+    fn patterns() {
+        let p = pat!("[a-z]{2,4}[0-9]+");
+        assert_eq!( Ok((&b"ab12"[..],                &b" x"[..])), p.parse(b"ab12 x"));
+        assert_eq!( Ok((&b"abcd7"[..],               &b""  [..])), p.parse(b"abcd7"));
+        assert_eq!(Err((ParseError::UnexpectedChar,  &b"e7"[..])), p.parse(b"abcde7"));
+        assert_eq!(Err((ParseError::UnexpectedChar,  &b"1" [..])), p.parse(b"a1"));
+        assert_eq!(Err((ParseError::EmptyInput,      &b""  [..])), p.parse(b"ab"));
+        assert_eq!( Ok((&b"x.y"[..],                 &b"\n"[..])), pat!(r"\w+\.?[^\s]*").parse(b"x.y\n"));
+        assert_eq!( Ok((&b""[..],                    &b""  [..])), pat!("-?.*").parse(b""));
+        assert_eq!( Ok((&b"-5"[..],                  &b""  [..])), pat!("-?.*").parse(b"-5"));
+        assert_eq!( Ok((&b"[x]"[..],                 &b""  [..])), pat!(r"\[[^\]]\]").parse(b"[x]"));
+    }
+}
@@ -0,0 +1,147 @@
+use super::*;
+use crate::astr::AString;
+use std::fmt::Debug;
+
+/// The counterpart of a `Parser`, writes a value in the format the parser
+/// accepts, so that a single grammar can be used to both parse and print.
+/// Implemented by the basic parsers, and by the combinators whose parts are
+/// printers. Alternatives such as `or` and `alt` print with the first branch
+/// that `can_print` the value.
+pub trait Printer<T: ?Sized> {
+    /// Writes `value`, may panic if the printer can't represent it.
+    fn print(&self, value: &T, output: &mut AString);
+
+    /// Whether `print` can write `value`, for example a `token((b'#', true))`
+    /// can only print `true`.
+    fn can_print(&self, _value: &T) -> bool {
+        true
+    }
+
+    fn print_to_string(&self, value: &T) -> AString {
+        let mut output = AString::new();
+        self.print(value, &mut output);
+        output
+    }
+}
+
+/// Prints the digits of `value` in the given radix, most significant first.
+pub(super) fn print_digits(mut value: u128, radix: u32, min_digits: usize, output: &mut AString) {
+    let start = output.len();
+    loop {
+        let digit = (value % radix as u128) as u32;
+        output.push(std::char::from_digit(digit, radix).unwrap() as u8);
+        value /= radix as u128;
+        if value == 0 && output.len() - start >= min_digits {
+            break;
+        }
+    }
+    output[start..].reverse();
+}
+
+/// Whether `value` can be written with at most `max_digits` digits in the
+/// given radix.
+pub(super) fn fits_digits(mut value: u128, radix: u32, max_digits: usize) -> bool {
+    let mut digits = 1;
+    while value >= radix as u128 {
+        value /= radix as u128;
+        digits += 1;
+    }
+    digits <= max_digits
+}
+
+/// Asserts that printing `value` and parsing the result gives back `value`.
+/// This checks a single value, call it once for each of the interesting cases
+/// of a grammar, such as every branch of an alternative.
+pub fn assert_round_trip<P, T>(grammar: &P, value: &T)
+where
+    P: Printer<T> + for<'s> Parser<'s, Output = T>,
+    T: PartialEq + Debug,
+{
+    let text = grammar.print_to_string(value);
+    match grammar.parse(&text) {
+        Ok((parsed, [])) => assert_eq!(
+            value,
+            &parsed,
+            "round trip through {:?} changed the value",
+            String::from_utf8_lossy(&text)
+        ),
+        Ok((_, remainder)) => panic!(
+            "printed {:?}, but parsing left {:?}",
+            String::from_utf8_lossy(&text),
+            String::from_utf8_lossy(remainder)
+        ),
+        Err((e, remainder)) => panic!(
+            "printed {:?}, but parsing failed: {e}, at: {:?}",
+            String::from_utf8_lossy(&text),
+            String::from_utf8_lossy(remainder)
+        ),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::grid::VecGrid;
+
+    #[test]
+    fn round_trips() {
+        let pair = number::<i32>().and(token(b',').then(hex::<u16>().fixed::<4>()));
+        let pairs = pair.sep_by::<_, Vec<_>>(token(b'\n'));
+        assert_eq!(
+            b"-12,00ff\n3,beef".to_vec(),
+            pairs.print_to_string(&vec![(-12, 0xff), (3, 0xbeef)])
+        );
+        assert_round_trip(&pairs, &vec![(i32::MIN, 0), (0, 0xffff)]);
+
+        let float = number::<f64>().trailed(token(b"m"));
+        assert_round_trip(&float, &-0.1);
+        assert_round_trip(&float, &1e-7);
+
+        let digits = grid::<VecGrid<u8>, _, _>(digit(), token(b'\n'));
+        assert_round_trip(&digits, &VecGrid::new((3, 2), |p| (p.x * 3 + p.y) as u8));
+
+        #[derive(Debug, PartialEq)]
+        struct Range(u8, u8);
+        let range = separated_pair(number(), token(b'-'), number())
+            .bimap(|(a, b)| Range(a, b), |r: &Range| (r.0, r.1));
+        assert_eq!(b"2-4".to_vec(), range.print_to_string(&Range(2, 4)));
+        assert_round_trip(&range, &Range(0, 255));
+    }
+
+    #[test]
+    fn unprintable_values() {
+        let two_digits = number::<u32>().fixed::<2>();
+        assert_round_trip(&two_digits, &99);
+        assert_eq!(b"07".to_vec(), two_digits.print_to_string(&7));
+        assert!(!two_digits.can_print(&123));
+        assert!(!hex::<i64>().fixed::<2>().can_print(&0x100));
+        assert!(!hex::<i64>().fixed::<2>().can_print(&-1));
+        let big = hex::<num::BigUint>().fixed::<4>();
+        assert_round_trip(&big, &0xffffu32.into());
+        assert!(!big.can_print(&0x10000u32.into()));
+
+        let float = number::<f64>();
+        assert!(float.can_print(&-1.5));
+        for value in [f64::NAN, f64::INFINITY, f64::NEG_INFINITY] {
+            assert!(!float.can_print(&value));
+        }
+    }
+
+    #[test]
+    fn alternatives() {
+        let cell = token((b'#', true)).or(token((b'.', false)));
+        assert_eq!(b"#".to_vec(), cell.print_to_string(&true));
+        assert_round_trip(&cell, &false);
+
+        let operator = alt((token((b'*', '*')), token((b'+', '+'))));
+        assert!(!operator.can_print(&'-'));
+        let operand = alt((token((b"old", None)), number::<u32>().opt()));
+        let operation = (token(b"new = old "), operator, token(b' '), operand);
+        assert_eq!(
+            b"new = old * old".to_vec(),
+            operation.print_to_string(&((), '*', (), None))
+        );
+        assert_round_trip(&operation, &((), '+', (), Some(3)));
+        assert_round_trip(&operation, &((), '*', (), None));
+    }
+}
@@ -0,0 +1,129 @@
+use super::util::{impl_for_tuples, parse_fully, split_line};
+use super::*;
+
+fn is_indented(line: &[u8]) -> bool {
+    matches!(line.first(), Some(b' ' | b'\t'))
+}
+
+/// Splits an indented `Key: value` line into its key and value.
+fn split_field(line: &[u8]) -> Option<(&[u8], &[u8])> {
+    let line = line.trim_ascii_start();
+    let colon = line.iter().position(|&c| c == b':')?;
+    let (key, value) = (&line[..colon], &line[colon + 1..]);
+    Some((key.trim_ascii_end(), value.trim_ascii_start()))
+}
+
+fn field_name(name: &[u8]) -> String {
+    String::from_utf8_lossy(name).into_owned()
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct Field<'k, P> {
+    name: &'k [u8],
+    parser: P,
+}
+
+/// A named field of a record, its value is parsed using `parser`.
+pub fn field<'k, 's, P: Parser<'s>>(name: &'k [u8], parser: P) -> Field<'k, P> {
+    Field { name, parser }
+}
+
+/// Parses a record made up of indented `Key: value` lines, such as:
+/// ```text
+///   Starting items: 79, 98
+///   Test: divisible by 23
+///     If true: throw to monkey 2
+/// ```
+/// Takes a tuple of fields, each of which must occur exactly once, in any
+/// order, and returns a tuple of their values in the order of the fields.
+/// Deeper indented lines are treated as fields as well. The record ends at the
+/// first line that is not indented, which is not consumed, nor is the newline
+/// preceding it.
+pub fn record<'s, T: RecordFields<'s>>(fields: T) -> Record<T> {
+    Record(fields)
+}
+
+pub trait RecordFields<'s> {
+    type Output: 's;
+    fn parse_record(&self, input: &'s [u8]) -> ParseResult<'s, Self::Output>;
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct Record<T>(T);
+impl<'s, T: RecordFields<'s>> Parser<'s> for Record<T> {
+    type Output = T::Output;
+
+    fn parse(&self, input: &'s [u8]) -> ParseResult<'s, Self::Output> {
+        self.0.parse_record(input)
+    }
+}
+
+macro_rules! impl_record_fields {
+    ($($parser:ident $output:ident $value:ident),+) => {
+        impl<'k, 's, $($parser: Parser<'s>),+> RecordFields<'s> for ($(Field<'k, $parser>,)+) {
+            type Output = ($($parser::Output,)+);
+
+            #[allow(non_snake_case)]
+            fn parse_record(&self, input: &'s [u8]) -> ParseResult<'s, Self::Output> {
+                let ($($parser,)+) = self;
+                $(let mut $output = None;)+
+                let mut remainder = input;
+                let mut line_start = input;
+                while is_indented(line_start) {
+                    let (line, after_line) = split_line(line_start);
+                    let (key, value) =
+                        split_field(line).ok_or((ParseError::ExpectedField, line_start))?;
+                    $(
+                        if key == $parser.name {
+                            if $output.is_some() {
+                                return Err((ParseError::DuplicateField(field_name(key)), line_start));
+                            }
+                            $output = Some(parse_fully(
+                                &$parser.parser,
+                                value,
+                                EndOfInput::TrailingWhitespace,
+                                ParseError::FieldNotConsumed,
+                            )?);
+                        } else
+                    )+
+                    {
+                        return Err((ParseError::UnknownField(field_name(key)), line_start));
+                    }
+                    remainder = after_line;
+                    match after_line {
+                        [b'\n', next @ ..] => line_start = next,
+                        _ => break,
+                    }
+                }
+                $(
+                    let $output = $output
+                        .ok_or_else(|| (ParseError::MissingField(field_name($parser.name)), input))?;
+                )+
+                Ok((($($output,)+), remainder))
+            }
+        }
+    };
+}
+
+impl_for_tuples!(impl_record_fields);
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    #[rustfmt::skip]
+    fn records() {
+        let p = record((
+            field(b"Size", number::<u32>()),
+            field(b"Name", take_while((), |_, c| c.is_ascii_alphabetic())),
+        ));
+        assert_eq!( Ok(((3, &b"abc"[..]),                        &b"\nEnd"[..])), p.parse(b"  Name: abc\n    Size: 3\nEnd"));
+        assert_eq!( Ok(((3, &b"abc"[..]),                        &b""     [..])), p.parse(b"  Size:3 \n  Name: abc"));
+        assert_eq!(Err((ParseError::MissingField("Name".into()), &b"  Size: 3\n"[..])), p.parse(b"  Size: 3\n"));
+        assert_eq!(Err((ParseError::UnknownField("Age".into()),  &b"  Age: 3"[..])), p.parse(b"  Size: 3\n  Age: 3"));
+        assert_eq!(Err((ParseError::DuplicateField("Size".into()), &b"  Size: 4"[..])), p.parse(b"  Size: 3\n  Size: 4"));
+        assert_eq!(Err((ParseError::FieldNotConsumed,            &b"x"    [..])), p.parse(b"  Size: 3x"));
+        assert_eq!(Err((ParseError::ExpectedField,               &b"  Size"[..])), p.parse(b"  Size"));
+    }
+}
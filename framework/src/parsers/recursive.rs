@@ -0,0 +1,137 @@
+use super::*;
+use std::{
+    cell::{Cell, OnceCell},
+    rc::{Rc, Weak},
+};
+
+/// The default maximum nesting depth of a recursive parser.
+pub const DEFAULT_MAX_DEPTH: usize = 256;
+
+struct Inner<'s, T> {
+    parser: OnceCell<Box<dyn Parser<'s, Output = T> + 's>>,
+    depth: Cell<usize>,
+    max_depth: Cell<usize>,
+}
+
+/// A parser that can refer to itself, created by `recursive`.
+pub struct Recursive<'s, T> {
+    inner: Rc<Inner<'s, T>>,
+}
+
+/// A reference to the recursive parser which is being defined, it can be used
+/// as a parser within its own definition.
+pub struct RecursiveRef<'s, T> {
+    inner: Weak<Inner<'s, T>>,
+}
+
+/// Creates a parser for recursive grammars. The closure receives a reference
+/// to the parser that is being created, which it can use within the parser it
+/// returns. Nesting deeper than `DEFAULT_MAX_DEPTH` results in a
+/// `ParseError::RecursionLimitExceeded`, rather than overflowing the stack.
+pub fn recursive<'s, T, P, F>(f: F) -> Recursive<'s, T>
+where
+    T: 's,
+    P: Parser<'s, Output = T> + 's,
+    F: FnOnce(RecursiveRef<'s, T>) -> P,
+{
+    let inner = Rc::new(Inner {
+        parser: OnceCell::new(),
+        depth: Cell::new(0),
+        max_depth: Cell::new(DEFAULT_MAX_DEPTH),
+    });
+    let parser = f(RecursiveRef {
+        inner: Rc::downgrade(&inner),
+    });
+    _ = inner.parser.set(Box::new(parser));
+    Recursive { inner }
+}
+
+impl<'s, T> Recursive<'s, T> {
+    /// Sets the maximum nesting depth of this parser.
+    pub fn max_depth(self, max_depth: usize) -> Self {
+        self.inner.max_depth.set(max_depth);
+        self
+    }
+}
+
+impl<'s, T: 's> Inner<'s, T> {
+    fn parse(&self, input: &'s [u8]) -> ParseResult<'s, T> {
+        let parser = self
+            .parser
+            .get()
+            .expect("recursive parser used during its own construction");
+        let depth = self.depth.get();
+        if depth >= self.max_depth.get() {
+            return Err((ParseError::RecursionLimitExceeded, input));
+        }
+        self.depth.set(depth + 1);
+        let result = parser.parse(input);
+        self.depth.set(depth);
+        result
+    }
+}
+
+impl<'s, T: 's> Parser<'s> for Recursive<'s, T> {
+    type Output = T;
+
+    fn parse(&self, input: &'s [u8]) -> ParseResult<'s, Self::Output> {
+        self.inner.parse(input)
+    }
+}
+
+impl<'s, T: 's> Parser<'s> for RecursiveRef<'s, T> {
+    type Output = T;
+
+    fn parse(&self, input: &'s [u8]) -> ParseResult<'s, Self::Output> {
+        self.inner
+            .upgrade()
+            .expect("recursive parser used after it was dropped")
+            .parse(input)
+    }
+}
+
+impl<'s, T> Clone for Recursive<'s, T> {
+    fn clone(&self) -> Self {
+        Recursive {
+            inner: self.inner.clone(),
+        }
+    }
+}
+
+impl<'s, T> Clone for RecursiveRef<'s, T> {
+    fn clone(&self) -> Self {
+        RecursiveRef {
+            inner: self.inner.clone(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[derive(Debug, PartialEq, Eq)]
+    enum Tree {
+        Leaf(u8),
+        Node(Vec<Tree>),
+    }
+
+    #[test]
+    fn nesting() {
+        let tree = recursive(|tree| {
+            let node = delimited(token(b'('), tree.sep_by(token(b' ')), token(b')'));
+            number::<u8>().map(Tree::Leaf).or(node.map(Tree::Node))
+        })
+        .max_depth(3);
+
+        use Tree::*;
+        assert_eq!(
+            Ok((Node(vec![Leaf(1), Node(vec![Leaf(2)])]), &b""[..])),
+            tree.parse(b"(1 (2))")
+        );
+        assert_eq!(
+            Err((ParseError::RecursionLimitExceeded, &b"4)))"[..])),
+            tree.parse(b"(((4)))")
+        );
+    }
+}
@@ -0,0 +1,202 @@
+use super::util::{impl_for_tuples, parse_fully, split_off_line};
+use super::*;
+use std::marker::PhantomData;
+
+/// Whether a line only contains whitespace, this includes the `\r` of a CRLF
+/// line ending, so such lines separate sections just like empty lines.
+fn is_blank(line: &[u8]) -> bool {
+    line.iter().all(u8::is_ascii_whitespace)
+}
+
+/// Skips over any lines which are empty, or only contain whitespace.
+fn skip_blank_lines(mut input: &[u8]) -> &[u8] {
+    while !input.is_empty() {
+        let (line, remainder) = split_off_line(input);
+        if !is_blank(line) {
+            break;
+        }
+        input = remainder;
+    }
+    input
+}
+
+/// Splits off the next section, returning it without its trailing whitespace,
+/// and the remainder after the blank lines following it.
+fn split_section(input: &[u8]) -> Option<(&[u8], &[u8])> {
+    let input = skip_blank_lines(input);
+    if input.is_empty() {
+        return None;
+    }
+    let mut remainder = input;
+    while !remainder.is_empty() {
+        let (line, next) = split_off_line(remainder);
+        if is_blank(line) {
+            break;
+        }
+        remainder = next;
+    }
+    let section = &input[..input.len() - remainder.len()];
+    Some((section.trim_ascii_end(), skip_blank_lines(remainder)))
+}
+
+/// Iterator over the sections of a string, which are separated by one or more
+/// lines that are empty or only contain whitespace, created by
+/// `AStrExt::sections`. Sections do not include their trailing whitespace, so
+/// CRLF line endings are tolerated.
+#[derive(Debug, Clone)]
+pub struct Sections<'s> {
+    remainder: &'s [u8],
+}
+
+impl<'s> Sections<'s> {
+    pub fn new(input: &'s [u8]) -> Self {
+        Sections { remainder: input }
+    }
+}
+
+impl<'s> Iterator for Sections<'s> {
+    type Item = &'s [u8];
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (section, remainder) = split_section(self.remainder)?;
+        self.remainder = remainder;
+        Some(section)
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct SectionsParser<P, C> {
+    parser: P,
+    _collection: PhantomData<C>,
+}
+
+/// Applies the parser to each blank line separated section of the input, and
+/// returns a collection of the outputs. Each section must be fully consumed,
+/// and there must be at least one section.
+pub fn sections<'s, P, C>(parser: P) -> SectionsParser<P, C>
+where
+    P: Parser<'s>,
+    C: Default + Extend<P::Output>,
+{
+    SectionsParser {
+        parser,
+        _collection: PhantomData,
+    }
+}
+
+impl<'s, P, C> Parser<'s> for SectionsParser<P, C>
+where
+    P: Parser<'s>,
+    C: 's + Default + Extend<P::Output>,
+{
+    type Output = C;
+
+    fn parse(&self, input: &'s [u8]) -> ParseResult<'s, Self::Output> {
+        let (section, mut remainder) =
+            split_section(input).ok_or((ParseError::EmptyInput, input))?;
+        let mut elements = C::default();
+        elements.extend(Some(parse_fully(
+            &self.parser,
+            section,
+            EndOfInput::TrailingWhitespace,
+            ParseError::SectionNotConsumed,
+        )?));
+        while let Some((section, new_remainder)) = split_section(remainder) {
+            elements.extend(Some(parse_fully(
+                &self.parser,
+                section,
+                EndOfInput::TrailingWhitespace,
+                ParseError::SectionNotConsumed,
+            )?));
+            remainder = new_remainder;
+        }
+        Ok((elements, remainder))
+    }
+}
+
+/// Applies each parser in a tuple of parsers to the next blank line separated
+/// section of the input, and returns a tuple of their outputs. Each section
+/// must be fully consumed.
+pub fn sections_tuple<'s, T: SectionParsers<'s>>(parsers: T) -> SectionsTuple<T> {
+    SectionsTuple(parsers)
+}
+
+pub trait SectionParsers<'s> {
+    type Output: 's;
+    fn parse_sections(&self, input: &'s [u8]) -> ParseResult<'s, Self::Output>;
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct SectionsTuple<T>(T);
+impl<'s, T: SectionParsers<'s>> Parser<'s> for SectionsTuple<T> {
+    type Output = T::Output;
+
+    fn parse(&self, input: &'s [u8]) -> ParseResult<'s, Self::Output> {
+        self.0.parse_sections(input)
+    }
+}
+
+macro_rules! impl_section_parsers {
+    ($($parser:ident $output:ident $value:ident),+) => {
+        impl<'s, $($parser: Parser<'s>),+> SectionParsers<'s> for ($($parser,)+) {
+            type Output = ($($parser::Output,)+);
+
+            #[allow(non_snake_case)]
+            fn parse_sections(&self, input: &'s [u8]) -> ParseResult<'s, Self::Output> {
+                let ($($parser,)+) = self;
+                let remainder = input;
+                $(
+                    let (section, remainder) =
+                        split_section(remainder).ok_or((ParseError::EmptyInput, remainder))?;
+                    let $output = parse_fully(
+                        $parser,
+                        section,
+                        EndOfInput::TrailingWhitespace,
+                        ParseError::SectionNotConsumed,
+                    )?;
+                )+
+                Ok((($($output,)+), remainder))
+            }
+        }
+    };
+}
+
+impl_for_tuples!(impl_section_parsers);
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn splitting() {
+        let input = b"\n a\r\nb \r\n\r\n  \t\nc\n\n\nd\r\n\r\n";
+        assert_eq!(
+            vec![&b" a\r\nb"[..], b"c", b"d"],
+            input.sections().collect::<Vec<_>>()
+        );
+        assert_eq!(0, b"\n \n".sections().count());
+    }
+
+    #[test]
+    fn crlf() {
+        let nrs = number::<u32>().sep_by::<_, Vec<_>>(token(b"\r\n"));
+        let input = b"1\r\n2\r\n\r\n3\r\n \t\r\n4\r\n";
+        assert_eq!(
+            Ok((vec![vec![1, 2], vec![3], vec![4]], &b""[..])),
+            sections(nrs).parse(input)
+        );
+    }
+
+    #[test]
+    #[rustfmt::skip]
+    fn parsing() {
+        let nrs = number::<u32>().sep_by::<_, Vec<_>>(token(b'\n'));
+        assert_eq!( Ok((vec![vec![1, 2], vec![3]],       &b""    [..])), sections(nrs.clone()).parse(b"1\n2\n\n3\n"));
+        assert_eq!(Err((ParseError::SectionNotConsumed,  &b"\nx" [..])), sections::<_, Vec<_>>(nrs.clone()).parse(b"1\nx\n\n3"));
+        assert_eq!(Err((ParseError::EmptyInput,          &b" \n" [..])), sections::<_, Vec<_>>(nrs.clone()).parse(b" \n"));
+        let p = sections_tuple((nrs.clone(), token(b"end")));
+        assert_eq!( Ok(((vec![1, 2], ()),                &b""    [..])), p.parse(b"1\n2\r\n\r\nend\r\n"));
+        assert_eq!( Ok(((vec![1, 2], ()),                &b"more"[..])), p.parse(b"1\n2\n\nend\n\nmore"));
+        assert_eq!(Err((ParseError::EmptyInput,          &b""    [..])), p.parse(b"1\n2\n\n"));
+    }
+}
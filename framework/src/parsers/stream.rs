@@ -0,0 +1,208 @@
+use super::util::parse_fully;
+use super::*;
+use crate::result::Result;
+use std::io::{BufRead, Read};
+
+/// The default maximum length of a line read by `stream_lines`, in bytes.
+pub const DEFAULT_MAX_LINE_LENGTH: usize = 1 << 20;
+
+/// Reads lines from `reader` one at a time, and applies the parser to each of
+/// them, yielding the outputs. Only the current line is kept in memory, so the
+/// input can be much larger than the available memory. Like `lines_par`, each
+/// line must be fully consumed, and a trailing newline is allowed. A `\r`
+/// before the newline is removed, so CRLF line endings are accepted.
+///
+/// Parse errors are wrapped in `ParseError::AtPosition`, with the 1-based line
+/// and column at which the parser failed, after which the next line is parsed.
+/// The iterator ends after an IO error, or a line longer than
+/// `DEFAULT_MAX_LINE_LENGTH`, which can be changed with `max_line_length`.
+pub fn stream_lines<R, P, T>(reader: R, parser: P) -> StreamLines<R, P>
+where
+    R: BufRead,
+    P: for<'s> Parser<'s, Output = T>,
+{
+    StreamLines {
+        reader,
+        parser,
+        line: Vec::new(),
+        line_number: 0,
+        max_line_length: DEFAULT_MAX_LINE_LENGTH,
+        done: false,
+    }
+}
+
+pub struct StreamLines<R, P> {
+    reader: R,
+    parser: P,
+    line: Vec<u8>,
+    line_number: usize,
+    max_line_length: usize,
+    done: bool,
+}
+
+impl<R, P> StreamLines<R, P> {
+    /// Fails with `ParseError::LineTooLong` on a line longer than `length`
+    /// bytes, excluding the line ending, instead of buffering it entirely.
+    pub fn max_line_length(self, length: usize) -> Self {
+        StreamLines {
+            max_line_length: length,
+            ..self
+        }
+    }
+
+    /// Reads the next line into the buffer, without its newline. Returns
+    /// `false` at the end of the input.
+    fn read_line(&mut self) -> Result<bool>
+    where
+        R: BufRead,
+    {
+        self.line.clear();
+        // Two extra bytes for the line ending.
+        let read = (&mut self.reader)
+            .take((self.max_line_length as u64).saturating_add(2))
+            .read_until(b'\n', &mut self.line)?;
+        if read == 0 {
+            return Ok(false);
+        }
+        self.line_number += 1;
+        if self.line.last() == Some(&b'\n') {
+            self.line.pop();
+        }
+        if self.line.last() == Some(&b'\r') {
+            self.line.pop();
+        }
+        if self.line.len() > self.max_line_length {
+            let error = ParseError::LineTooLong(self.max_line_length);
+            return Err(ParseError::AtLine(self.line_number, Box::new(error)).into());
+        }
+        Ok(true)
+    }
+}
+
+impl<R, P, T> Iterator for StreamLines<R, P>
+where
+    R: BufRead,
+    P: for<'s> Parser<'s, Output = T>,
+{
+    type Item = Result<T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+        match self.read_line() {
+            Ok(true) => {}
+            Ok(false) => {
+                self.done = true;
+                return None;
+            }
+            Err(e) => {
+                self.done = true;
+                return Some(Err(e));
+            }
+        }
+
+        let line = &self.line[..];
+        let end = EndOfInput::Strict;
+        let (e, remainder) = match parse_fully(&self.parser, line, end, ParseError::LineNotConsumed)
+        {
+            Ok(output) => return Some(Ok(output)),
+            Err(e) => e,
+        };
+        let column = line.len().saturating_sub(remainder.len()) + 1;
+        let error = ParseError::AtPosition(self.line_number, column, Box::new(e));
+        Some(Err(error.into()))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::error::Error;
+    use std::io::BufReader;
+
+    fn parse_errors<T>(results: Vec<Result<T>>) -> Vec<Option<ParseError>> {
+        results
+            .into_iter()
+            .map(|result| match result {
+                Err(Error::ParseError(e)) => Some(e),
+                _ => None,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn streaming() {
+        let pair = || number::<u32>().and(token(b',').then(number::<u32>()));
+        let input = (0..1000)
+            .map(|i| format!("{i},{}\n", i * 2))
+            .collect::<String>();
+        // A tiny buffer, so that lines span multiple reads.
+        let reader = BufReader::with_capacity(3, input.as_bytes());
+        let pairs = stream_lines(reader, pair()).collect::<Result<Vec<_>>>();
+        assert_eq!(
+            (0..1000).map(|i| (i, i * 2)).collect::<Vec<_>>(),
+            pairs.unwrap()
+        );
+
+        let results = stream_lines(&b"1,2\n3,x\n5,6 \n7,8"[..], pair()).collect();
+        assert_eq!(
+            vec![
+                None,
+                Some(ParseError::AtPosition(
+                    2,
+                    3,
+                    Box::new(ParseError::ExpectedDigit)
+                )),
+                Some(ParseError::AtPosition(
+                    3,
+                    4,
+                    Box::new(ParseError::LineNotConsumed)
+                )),
+                None,
+            ],
+            parse_errors(results)
+        );
+
+        let results = stream_lines(&b"1,2\n300000,1\n5,6"[..], pair())
+            .max_line_length(5)
+            .collect();
+        assert_eq!(
+            vec![
+                None,
+                Some(ParseError::AtLine(2, Box::new(ParseError::LineTooLong(5)))),
+            ],
+            parse_errors(results)
+        );
+    }
+
+    #[test]
+    fn line_endings_and_limits() {
+        let results = stream_lines(&b"1\r\n22\r\n333\r\n"[..], number::<u32>())
+            .max_line_length(2)
+            .collect();
+        assert_eq!(
+            vec![
+                None,
+                None,
+                Some(ParseError::AtLine(3, Box::new(ParseError::LineTooLong(2)))),
+            ],
+            parse_errors(results)
+        );
+
+        let long_line = vec![b'1'; DEFAULT_MAX_LINE_LENGTH + 1];
+        let results = stream_lines(&long_line[..], number::<u32>()).collect();
+        assert_eq!(
+            vec![Some(ParseError::AtLine(
+                1,
+                Box::new(ParseError::LineTooLong(DEFAULT_MAX_LINE_LENGTH))
+            ))],
+            parse_errors(results)
+        );
+
+        let results = stream_lines(&b"1\n22"[..], number::<u32>())
+            .max_line_length(usize::MAX)
+            .collect::<Result<Vec<_>>>();
+        assert_eq!(vec![1, 22], results.unwrap());
+    }
+}
@@ -1,9 +1,15 @@
+use super::numbers::IsParsableInteger;
+use super::{EndOfInput, Ints, ParseError, Parser, Sections};
 use crate::astr::AStr;
 use std::slice::Split;
 
 pub trait AStrExt {
     fn lines(&self) -> Split<'_, u8, for<'r> fn(&'r u8) -> bool>;
     fn split_space(&self) -> Split<'_, u8, for<'r> fn(&'r u8) -> bool>;
+    /// Iterates over all integers, skipping any other bytes, see `Ints`.
+    fn ints<T: IsParsableInteger>(&self) -> Ints<'_, T>;
+    /// Iterates over blank line separated sections, see `Sections`.
+    fn sections(&self) -> Sections<'_>;
 }
 
 impl AStrExt for AStr {
@@ -13,4 +19,65 @@ impl AStrExt for AStr {
     fn split_space(&self) -> Split<'_, u8, for<'r> fn(&'r u8) -> bool> {
         self.split(|&l| l == b' ')
     }
+    fn ints<T: IsParsableInteger>(&self) -> Ints<'_, T> {
+        Ints::new(self)
+    }
+    fn sections(&self) -> Sections<'_> {
+        Sections::new(self)
+    }
+}
+
+/// Returns the current line excluding the newline, and the remainder starting
+/// at the newline.
+pub(super) fn split_line(input: &[u8]) -> (&[u8], &[u8]) {
+    let end = input
+        .iter()
+        .position(|&c| c == b'\n')
+        .unwrap_or(input.len());
+    input.split_at(end)
+}
+
+/// Returns the current line excluding the newline, and the remainder after
+/// the newline.
+pub(super) fn split_off_line(input: &[u8]) -> (&[u8], &[u8]) {
+    let (line, remainder) = split_line(input);
+    (line, remainder.get(1..).unwrap_or(remainder))
 }
+
+/// Applies the parser to a part of the input, such as a line or a section,
+/// which it must consume fully, except for what `end` allows. Otherwise fails
+/// with `error` at the remainder.
+pub(super) fn parse_fully<'s, P: Parser<'s>>(
+    parser: &P,
+    input: &'s [u8],
+    end: EndOfInput,
+    error: ParseError,
+) -> Result<P::Output, (ParseError, &'s [u8])> {
+    let (output, remainder) = parser.parse(input)?;
+    if !end.allows(remainder) {
+        return Err((error, remainder));
+    }
+    Ok(output)
+}
+
+/// Invokes the macro for tuples of one up to twelve elements, passing a type
+/// parameter for the parser, a variable for the output and a type parameter
+/// for the value of each element.
+macro_rules! impl_for_tuples {
+    ($impl:ident) => {
+        $impl!(P1 o1 T1);
+        $impl!(P1 o1 T1, P2 o2 T2);
+        $impl!(P1 o1 T1, P2 o2 T2, P3 o3 T3);
+        $impl!(P1 o1 T1, P2 o2 T2, P3 o3 T3, P4 o4 T4);
+        $impl!(P1 o1 T1, P2 o2 T2, P3 o3 T3, P4 o4 T4, P5 o5 T5);
+        $impl!(P1 o1 T1, P2 o2 T2, P3 o3 T3, P4 o4 T4, P5 o5 T5, P6 o6 T6);
+        $impl!(P1 o1 T1, P2 o2 T2, P3 o3 T3, P4 o4 T4, P5 o5 T5, P6 o6 T6, P7 o7 T7);
+        $impl!(P1 o1 T1, P2 o2 T2, P3 o3 T3, P4 o4 T4, P5 o5 T5, P6 o6 T6, P7 o7 T7, P8 o8 T8);
+        $impl!(P1 o1 T1, P2 o2 T2, P3 o3 T3, P4 o4 T4, P5 o5 T5, P6 o6 T6, P7 o7 T7, P8 o8 T8, P9 o9 T9);
+        $impl!(P1 o1 T1, P2 o2 T2, P3 o3 T3, P4 o4 T4, P5 o5 T5, P6 o6 T6, P7 o7 T7, P8 o8 T8, P9 o9 T9, P10 o10 T10);
+        $impl!(P1 o1 T1, P2 o2 T2, P3 o3 T3, P4 o4 T4, P5 o5 T5, P6 o6 T6, P7 o7 T7, P8 o8 T8, P9 o9 T9, P10 o10 T10, P11 o11 T11);
+        $impl!(P1 o1 T1, P2 o2 T2, P3 o3 T3, P4 o4 T4, P5 o5 T5, P6 o6 T6, P7 o7 T7, P8 o8 T8, P9 o9 T9, P10 o10 T10, P11 o11 T11, P12 o12 T12);
+    };
+}
+
+pub(super) use impl_for_tuples;
@@ -0,0 +1,98 @@
+use super::combi::Trailed;
+use super::*;
+use crate::astr::AString;
+
+#[derive(Debug, Clone, Copy)]
+pub struct Whitespace {
+    newlines: bool,
+}
+
+/// Skips over any ASCII whitespace, including newlines. Never fails.
+pub const fn ws() -> Whitespace {
+    Whitespace { newlines: true }
+}
+
+/// Skips over spaces and tabs, but not newlines. Never fails.
+pub const fn spaces() -> Whitespace {
+    Whitespace { newlines: false }
+}
+
+impl<'s> Parser<'s> for Whitespace {
+    type Output = ();
+
+    fn parse(&self, input: &'s [u8]) -> ParseResult<'s, Self::Output> {
+        let length = input
+            .iter()
+            .take_while(|&&c| match c {
+                b' ' | b'\t' => true,
+                b'\n' | b'\r' | b'\x0C' => self.newlines,
+                _ => false,
+            })
+            .count();
+        Ok(((), &input[length..]))
+    }
+}
+
+/// Prints nothing, the whitespace is optional.
+impl Printer<()> for Whitespace {
+    fn print(&self, _: &(), _: &mut AString) {}
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct Newline;
+
+/// Parses a line ending, either `\n` or `\r\n`.
+pub const fn newline() -> Newline {
+    Newline
+}
+
+impl<'s> Parser<'s> for Newline {
+    type Output = ();
+
+    fn parse(&self, input: &'s [u8]) -> ParseResult<'s, Self::Output> {
+        match input {
+            [b'\n', remainder @ ..] | [b'\r', b'\n', remainder @ ..] => Ok(((), remainder)),
+            [] => Err((ParseError::EmptyInput, input)),
+            _ => Err((ParseError::TokenDoesNotMatch, input)),
+        }
+    }
+}
+
+impl Printer<()> for Newline {
+    fn print(&self, _: &(), output: &mut AString) {
+        output.push(b'\n');
+    }
+}
+
+/// Applies the parser, and skips over any spaces and tabs following it, but
+/// not newlines.
+pub fn lexeme<'s, P: Parser<'s>>(parser: P) -> Trailed<P, Whitespace> {
+    parser.trailed(spaces())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn whitespace() {
+        let row = spaces().then(lexeme(number::<u32>()).repeat_into::<Vec<_>>());
+        let rows = row.sep_by::<_, Vec<_>>(newline());
+        assert_eq!(
+            Ok((vec![vec![1, 2], vec![3]], &b"\r\n \r\n"[..])),
+            rows.parse(b"  1 2 \r\n3\t\r\n \r\n")
+        );
+        assert_eq!(
+            Ok((7, &b""[..])),
+            ws().then(number::<u32>()).parse(b" \r\n\t7")
+        );
+        assert!(rows.execute(b"1 2\r\n3\r\n \r\n").is_err());
+        assert!(rows
+            .execute_with(b"1\r\n3\r\n\r\n\n", EndOfInput::TrailingBlankLines)
+            .is_ok());
+        assert!(rows
+            .execute_with(b"1\r\n3\r\n \r\n", EndOfInput::TrailingWhitespace)
+            .is_ok());
+        assert!(rows.execute_with(b"1\n", EndOfInput::Strict).is_err());
+    }
+}
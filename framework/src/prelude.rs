@@ -2,7 +2,7 @@ pub use crate::astr::{AStr, AString};
 pub use crate::cbuffer::{CBuffer, CBufferMutator};
 pub use crate::error::Error;
 pub use crate::graph;
-pub use crate::grid::VecGrid;
+pub use crate::grid::{BitGrid, SparseGrid, VecGrid, VecGrid3};
 pub use crate::iter::{Distinct, DistinctResult, IteratorExt, LendingIterator, SizedIteratorExt};
 pub use crate::ocr;
 pub use crate::offsets::{Neighbor, Neighbors, NeighborsAlong, Offset};
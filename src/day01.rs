@@ -20,7 +20,7 @@ fn parse(input: &[u8]) -> Result<Vec<Vec<u32>>> {
     use parsers::*;
     let nr = number::<u32>();
     let elf = nr.sep_by(token(b'\n'));
-    elf.sep_by(token(b"\n\n")).execute(input)
+    sections(elf).execute(input)
 }
 
 tests! {
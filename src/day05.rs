@@ -48,41 +48,12 @@ fn pt2(input: &Input) -> Result<AString> {
 
 fn parse(input: &[u8]) -> Result<Input> {
     use parsers::*;
-    fn parse_stacks(input: &[u8]) -> Result<(Stacks, &[u8])> {
-        let tower_lines = input.lines().take_while(|l| !l.is_empty()).collect_vec();
-        if tower_lines.len() < 2 {
-            return Err(Error::InvalidInput("expected stacks"));
-        }
-        let last_line = tower_lines[tower_lines.len() - 1];
-        if last_line.len() % 4 != 3 {
-            return Err(Error::InvalidInput("no stack numbering"));
-        }
-        let mut stacks = vec![Vec::new(); last_line.len() / 4 + 1];
-        for (line_index, line) in tower_lines.iter().rev().skip(1).enumerate() {
-            if line.len() != last_line.len() {
-                return Err(Error::InvalidInput("invalid line length"));
-            }
-            for (index, stack) in stacks.iter_mut().enumerate() {
-                let start = index * 4;
-                if &line[start..start + 3] == b"   " {
-                    continue;
-                }
-                if line[start] != b'[' || line[start + 2] != b']' {
-                    return Err(Error::ParseError(ParseError::TokenDoesNotMatch));
-                }
-                if stack.len() != line_index {
-                    return Err(Error::InvalidInput("stack has a gap"));
-                }
-                stack.push(line[start + 1]);
-            }
-        }
-        Ok((
-            stacks,
-            &input[(last_line.len() + 1) * tower_lines.len() + 1..],
-        ))
-    }
-
-    let (stacks, remainder) = parse_stacks(input)?;
+    let stacks = columns(
+        delimited(token(b'['), any(), token(b']')),
+        number::<u8>(),
+        4,
+        0,
+    );
     let nr = number::<u8>();
     let idx = nr.map_res(|n| n.checked_sub(1).ok_or(ParseError::Overflow));
     let moves = token(b"move ")
@@ -90,9 +61,10 @@ fn parse(input: &[u8]) -> Result<Input> {
         .and(token(b" from ").then(idx))
         .and(token(b" to ").then(idx))
         .map(|((count, from), to)| Move { count, from, to })
-        .sep_by(token(b'\n'))
-        .execute(remainder)?;
-    Ok(Input { stacks, moves })
+        .sep_by(token(b'\n'));
+    sections_tuple((stacks, moves))
+        .map(|(stacks, moves)| Input { stacks, moves })
+        .execute(input)
 }
 
 tests! {
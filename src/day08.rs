@@ -2,14 +2,9 @@ framework::day!(08, parse => pt1, pt2);
 
 type Grid = VecGrid<u8>;
 
-fn iter_cells(grid: &Grid) -> impl Iterator<Item = Vec2<usize>> + '_ {
-    (0..grid.height()).flat_map(|y| (0..grid.width()).map(move |x| (x, y).into()))
-}
-
 fn pt1(grid: &Grid) -> usize {
-    iter_cells(grid)
-        .filter(|&position| {
-            let height = grid[position];
+    grid.iter()
+        .filter(|&(position, &height)| {
             Offset::ORTHOGONAL.into_iter().any(|dir| {
                 position
                     .neighbors_along(dir)
@@ -38,8 +33,9 @@ fn count_trees_seen_in_dir(grid: &Grid, dir: Offset, position: Vec2<usize>) -> u
 }
 
 fn pt2(grid: &Grid) -> MulOutput<[usize; 4]> {
-    let res = iter_cells(grid)
-        .map(|position| -> [usize; 4] {
+    let res = grid
+        .iter()
+        .map(|(position, _)| -> [usize; 4] {
             Offset::ORTHOGONAL
                 .into_iter()
                 .map(|dir| count_trees_seen_in_dir(grid, dir, position))
@@ -46,13 +46,7 @@ fn pt2(moves: &[Move]) -> usize {
 
 fn parse(input: &[u8]) -> Result<Vec<Move>> {
     use parsers::*;
-    let dir = any().map_res(|f| match f {
-        b'R' => Ok(Offset::X_POS),
-        b'L' => Ok(Offset::X_NEG),
-        b'D' => Ok(Offset::Y_POS),
-        b'U' => Ok(Offset::Y_NEG),
-        _ => Err(ParseError::TokenDoesNotMatch),
-    });
+    let dir = udlr();
     let dist = number::<u32>();
     let mv = dir.and(token(b' ').then(dist));
     let mv = mv.map(|(dir, dist)| Move { dir, dist });
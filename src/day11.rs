@@ -95,36 +95,45 @@ fn parse(input: &[u8]) -> Result<Vec<Monkey>> {
         .and(value)
         .map(|((a, o), b)| (a, o, b));
 
-    let monkey = (token(b":\n  Starting items: ").then(starting_items))
-        .and(token(b"\n  Operation: ").then(operation))
-        .and(token(b"\n  Test: divisible by ").then(number::<Int>()))
-        .and(token(b"\n    If true: throw to monkey ").then(number::<usize>()))
-        .and(token(b"\n    If false: throw to monkey ").then(number::<usize>()))
-        .map(
-            |((((starting_items, operation), denominator), if_divisible), if_not_divisible)| {
-                Monkey {
-                    starting_items,
-                    operation,
-                    denominator,
-                    if_divisible,
-                    if_not_divisible,
-                }
-            },
-        );
-
-    (token(b"Monkey ").then(number::<usize>()).and(monkey))
-        .trailed(token(b"\n\n").opt())
-        .fold(Some(Vec::new()), |monkeys, (monkey_index, monkey)| {
-            let mut monkeys = monkeys?;
-            if monkey_index == monkeys.len() {
-                monkeys.push(monkey);
-                Some(monkeys)
-            } else {
-                None
-            }
-        })
-        .map_res(|v| v.ok_or(ParseError::Custom("monkey index does not line up")))
-        .execute(input)
+    let monkey = record((
+        field(b"Starting items", starting_items),
+        field(b"Operation", operation),
+        field(b"Test", token(b"divisible by ").then(number::<Int>())),
+        field(
+            b"If true",
+            token(b"throw to monkey ").then(number::<usize>()),
+        ),
+        field(
+            b"If false",
+            token(b"throw to monkey ").then(number::<usize>()),
+        ),
+    ))
+    .map(
+        |(starting_items, operation, denominator, if_divisible, if_not_divisible)| Monkey {
+            starting_items,
+            operation,
+            denominator,
+            if_divisible,
+            if_not_divisible,
+        },
+    );
+
+    (token(b"Monkey ")
+        .then(number::<usize>())
+        .trailed(token(b":\n"))
+        .and(monkey))
+    .trailed(token(b"\n\n").opt())
+    .fold(Some(Vec::new()), |monkeys, (monkey_index, monkey)| {
+        let mut monkeys = monkeys?;
+        if monkey_index == monkeys.len() {
+            monkeys.push(monkey);
+            Some(monkeys)
+        } else {
+            None
+        }
+    })
+    .map_res(|v| v.ok_or(ParseError::Custom("monkey index does not line up")))
+    .execute(input)
 }
 
 tests! {
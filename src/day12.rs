@@ -1,44 +1,32 @@
 framework::day!(12, parse => pt1, pt2);
 
-fn get_path_length(
-    grid: &VecGrid<Cell>,
-    initial: Vec2<usize>,
-    mut is_done: impl FnMut(Cell) -> bool,
-) -> Option<usize> {
-    let mut visited = HashSet::new();
-    graph::bfs((initial, 0), |(p, cost), nodes| {
-        if !visited.insert(p) {
-            return None;
-        }
-        let cell = grid[p];
-        if is_done(cell) {
-            return Some(cost);
-        }
-        let min_elevation = cell.get_elevation().saturating_sub(1);
-        let next_cost = cost + 1;
-        nodes.extend(
-            Offset::ORTHOGONAL
-                .into_iter()
-                .filter_map(|dir| p.neighbor(dir))
-                .filter(|&p| {
-                    grid.get(p)
-                        .filter(|neighbor| neighbor.get_elevation() >= min_elevation)
-                        .is_some()
-                })
-                .map(|p| (p, next_cost)),
-        );
-        None
-    })
+/// The number of steps from each cell to the end, searching backwards from it.
+fn get_distances(heightmap: &Heightmap) -> VecGrid<Option<u32>> {
+    heightmap
+        .grid
+        .distances([heightmap.end], &Offset::ORTHOGONAL, |cell, neighbor| {
+            neighbor.get_elevation() >= cell.get_elevation().saturating_sub(1)
+        })
+}
+
+fn pt1(heightmap: &Heightmap) -> Result<u32> {
+    let distances = get_distances(heightmap);
+    distances[heightmap.start].ok_or(Error::NoSolution)
 }
 
-fn pt1(grid: &VecGrid<Cell>) -> Result<usize> {
-    let (end, _) = grid.iter().find(|(_, c)| matches!(c, Cell::End)).unwrap();
-    get_path_length(grid, end, |cell| cell == Cell::Start).ok_or(Error::NoSolution)
+fn pt2(heightmap: &Heightmap) -> Result<u32> {
+    let distances = get_distances(heightmap);
+    (heightmap.grid.iter())
+        .filter(|&(_, cell)| cell.get_elevation() == 0)
+        .filter_map(|(p, _)| distances[p])
+        .min()
+        .ok_or(Error::NoSolution)
 }
 
-fn pt2(grid: &VecGrid<Cell>) -> Result<usize> {
-    let (end, _) = grid.iter().find(|(_, c)| matches!(c, Cell::End)).unwrap();
-    get_path_length(grid, end, |cell| cell.get_elevation() == 0).ok_or(Error::NoSolution)
+struct Heightmap {
+    grid: VecGrid<Cell>,
+    start: Vec2<usize>,
+    end: Vec2<usize>,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -58,12 +46,13 @@ impl Cell {
     }
 }
 
-fn parse(input: &[u8]) -> Result<VecGrid<Cell>> {
+fn parse(input: &[u8]) -> Result<Heightmap> {
     use parsers::*;
-    let start = token((b'S', Cell::Start));
-    let end = token((b'E', Cell::End));
     let letter = pattern!(b'a'..=b'z').map(|c| Cell::Letter(c - b'a'));
-    grid(letter.or(start).or(end), token(b'\n')).execute(input)
+    grid(letter, token(b'\n'))
+        .markers([(b'S', Cell::Start), (b'E', Cell::End)])
+        .map(|(grid, [start, end])| Heightmap { grid, start, end })
+        .execute(input)
 }
 
 tests! {
@@ -66,20 +66,16 @@ impl Ord for Packet {
 
 fn parse(input: &[u8]) -> Result<Vec<(Packet, Packet)>> {
     use parsers::*;
-    fn list(input: &[u8]) -> ParseResult<'_, Packet> {
-        let (o, s, c) = (token(b'['), token(b','), token(b']'));
-        (o.then(packet.sep_by(s).opt()).trailed(c))
-            .map(Option::unwrap_or_default)
-            .map(Packet::List)
-            .parse(input)
-    }
-    fn packet(input: &[u8]) -> ParseResult<'_, Packet> {
-        let nr = number::<Int>().map(Packet::Number);
-        nr.or(list).parse(input)
-    }
-
-    let parser = packet.and(token(b'\n').then(packet)).sep_by(token(b"\n\n"));
-    parser.execute(input)
+    let packet = recursive(|packet| {
+        let empty = token((b"[]", Vec::new()));
+        let list = empty
+            .or(delimited(token(b'['), packet.sep_by(token(b',')), token(b']')))
+            .map(Packet::List);
+        number::<Int>().map(Packet::Number).or(list)
+    });
+
+    let pair = separated_pair(packet.clone(), token(b'\n'), packet);
+    sections(pair).execute(input)
 }
 
 tests! {
@@ -111,4 +107,12 @@ tests! {
 
     test_pt!(parse, pt1, EXAMPLE => 13);
     test_pt!(parse, pt2, EXAMPLE => MulOutput([10, 14]));
+
+    #[test]
+    fn nesting_limit() {
+        let nested = [&[b'['; 300][..], &[b']'; 300][..]].concat();
+        let input = [&nested[..], b"\n[]"].concat();
+        let error = parse(&input).err().unwrap().to_string();
+        assert!(error.contains("recursion depth limit exceeded"), "{error}");
+    }
 }
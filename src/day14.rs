@@ -1,7 +1,7 @@
 framework::day!(14, parse => pt1, pt2);
 type Path = Vec<Vec2>;
-type Vec2 = framework::vecs::Vec2<usize>;
-type Grid = VecGrid<Cell>;
+type Vec2 = framework::vecs::Vec2<i32>;
+type Grid = SparseGrid<Cell>;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 enum Cell {
@@ -10,105 +10,54 @@ enum Cell {
     Sand,
 }
 
-fn simulate<const PADDING: usize>(
-    paths: &[Path],
-    mut process_grid: impl FnMut(&mut Grid),
-) -> Result<Grid> {
-    let (min, max) = paths.iter().flat_map(|path| path.iter()).fold(
-        (Vec2::new(500, 0), Vec2::new(500, 0)),
-        |(min, max), &curr| (min.min_comp(curr), max.max_comp(curr)),
-    );
-    let (min, max) = (
-        min - Vec2::new(PADDING, 0),
-        max + Vec2::new(PADDING * 2, PADDING),
-    );
-    let size = max + 1 - min;
-    let mut grid = VecGrid::new(size, |_| Cell::Air);
-    for (from, to) in paths
-        .iter()
-        .flat_map(|path| path.iter().map(|&p| p - min).tuple_windows())
-    {
-        if from.x == to.x {
-            let (min, max) = from.y.minmax(to.y);
-            for y in min..max + 1 {
-                grid[(from.x, y)] = Cell::Rock;
-            }
-        } else {
-            let (min, max) = from.x.minmax(to.x);
-            for x in min..max + 1 {
-                grid[(x, from.y)] = Cell::Rock;
+/// Pours sand until it falls below the lowest rock, or, with a `floor` two
+/// below the lowest rock, until the source is blocked. Returns the number of
+/// units of sand that came to rest.
+fn simulate(paths: &[Path], floor: bool) -> usize {
+    let mut grid = Grid::new(Cell::Air);
+    for (from, to) in paths.iter().flat_map(|path| path.iter().tuple_windows()) {
+        let (min, max) = (from.min_comp(*to), from.max_comp(*to));
+        for y in min.y..=max.y {
+            for x in min.x..=max.x {
+                grid[(x, y)] = Cell::Rock;
             }
         }
     }
+    let bottom = grid.bounds().map_or(0, |(_, max)| max.y);
 
-    process_grid(&mut grid);
-    let origin = (Vec2::new(500, 0) - min).to_usize();
-
-    'spawning: loop {
-        let mut p = origin;
-        'fall: loop {
-            for offset in [Offset::Y_POS, Offset::X_NEG_Y_POS, Offset::X_POS_Y_POS] {
-                let new_p = if let Some(p) = p.neighbor(offset) {
-                    p
-                } else {
-                    break 'spawning;
-                };
-                match grid.get(new_p) {
-                    Some(Cell::Air) => {
-                        p = new_p;
-                        continue 'fall;
-                    }
-                    Some(_) => continue,
-                    None => break 'spawning,
-                }
-            }
-            break;
-        }
-        if let Some(cell) = grid.get_mut(p) {
-            if *cell == Cell::Sand {
-                break;
+    // The path of the falling unit of sand, the next unit follows the same path
+    // up to where the previous one came to rest.
+    let mut path = vec![Vec2::new(500, 0)];
+    let mut count = 0;
+    while let Some(&p) = path.last() {
+        let next = [0, -1, 1]
+            .map(|dx| p + Vec2::new(dx, 1))
+            .into_iter()
+            .find(|&next| grid[next] == Cell::Air && !(floor && next.y == bottom + 2));
+        match next {
+            Some(next) if !floor && next.y > bottom => break,
+            Some(next) => path.push(next),
+            None => {
+                grid[p] = Cell::Sand;
+                count += 1;
+                path.pop();
             }
-            *cell = Cell::Sand;
-            continue 'spawning;
         }
-        break;
     }
-
-    Ok(grid)
+    count
 }
 
-fn pt1(paths: &[Path]) -> Result<usize> {
-    let grid = simulate::<0>(paths, |_| {})?;
-    Ok(grid.cells().iter().filter(|c| **c == Cell::Sand).count())
+fn pt1(paths: &[Path]) -> usize {
+    simulate(paths, false)
 }
 
-fn pt2(paths: &[Path]) -> Result<usize> {
-    let grid = simulate::<2>(paths, |grid| {
-        let bottom = grid.height() - 1;
-        for x in 0..grid.width() {
-            grid[(x, bottom)] = Cell::Rock;
-        }
-        let right = grid.width() - 1;
-        for y in 0..grid.height() {
-            grid[(0, y)] = Cell::Rock;
-            grid[(right, y)] = Cell::Rock;
-        }
-    })?;
-
-    let count_additional = |x: usize| {
-        let height = (0..grid.height() - 1)
-            .rev()
-            .take_while(|&y| grid[(x, y)] == Cell::Sand)
-            .count();
-        height * (height - 1) / 2
-    };
-    let additional = count_additional(1) + count_additional(grid.width() - 2);
-    Ok(grid.cells().iter().filter(|c| **c == Cell::Sand).count() + additional)
+fn pt2(paths: &[Path]) -> usize {
+    simulate(paths, true)
 }
 
 fn parse(input: &[u8]) -> Result<Vec<Path>> {
     use parsers::*;
-    let nr = number::<usize>();
+    let nr = number::<i32>();
     let point = nr.and(token(b',').then(nr)).map(Vec2::from);
     let path = point.sep_by(token(b" -> "));
     let paths = path.sep_by(token(b'\n'));
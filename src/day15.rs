@@ -141,14 +141,11 @@ struct Scan {
 
 fn parse(input: &[u8]) -> Result<Vec<Scan>> {
     use parsers::*;
-    let nr = number::<i32>();
-    let vec = token(b"x=")
-        .then(nr)
-        .and(token(b", y=").then(nr))
-        .map(Vec2::from);
-    let sensor = token(b"Sensor at ").then(vec);
-    let beacon = token(b": closest beacon is at ").then(vec);
-    let scan = (sensor.and(beacon)).map(|(sensor, beacon)| Scan { sensor, beacon });
+    let scan = parse_format!("Sensor at x={i32}, y={i32}: closest beacon is at x={i32}, y={i32}")
+        .map(|(sx, sy, bx, by)| Scan {
+            sensor: Vec2::new(sx, sy),
+            beacon: Vec2::new(bx, by),
+        });
     scan.sep_by(token(b'\n')).execute(input)
 }
 
@@ -198,31 +198,33 @@ struct Valve {
 
 fn parse(input: &[u8]) -> Result<(usize, Vec<Valve>)> {
     use parsers::*;
-    let letter = pattern!(b'A'..=b'Z').map(|l| l - b'A');
-    let name = letter.and(letter).map(|(a, b)| a as u16 * 26 + b as u16);
-    let connections = name.sep_by::<_, Vec<_>>(token(b", "));
+    let interner = Interner::new();
+    let name = pat!("[A-Z]+");
+    let connections = interner
+        .reference(name)
+        .map(|id| id as usize)
+        .sep_by::<_, Vec<_>>(token(b", "));
     let valve_token = token(b"; tunnels lead to valves ").or(token(b"; tunnel leads to valve "));
-    let descriptor = (token(b"Valve ").then(name))
+    let descriptor = (token(b"Valve ").then(interner.define(name)))
         .and(token(b" has flow rate=").then(number::<u32>()))
         .and(valve_token.then(connections));
-    let descriptors: Vec<_> = descriptor.sep_by(token(b'\n')).execute(input)?;
-    let mut name_to_index = HashMap::new();
-    for (i, ((name, _), _)) in descriptors.iter().enumerate() {
-        assert!(name_to_index.insert(*name, i).is_none());
-    }
-    let name_to_index = |name| name_to_index[&name];
+    let mut descriptors: Vec<_> = descriptor.sep_by(token(b'\n')).execute(input)?;
+
+    // Every valve is defined exactly once, so the ids are a permutation of the
+    // indices.
+    let symbols = interner.resolve()?;
+    descriptors.sort_unstable_by_key(|((id, _), _)| *id);
     let valves = descriptors
         .into_iter()
-        .map(|((_, flow_rate), connections)| {
-            let connections = connections.into_iter().map(name_to_index).collect();
-            Valve {
-                flow_rate,
-                connections,
-            }
+        .map(|((_, flow_rate), connections)| Valve {
+            flow_rate,
+            connections,
         })
         .collect();
-    let initial_index = name_to_index(0);
-    Ok((initial_index, valves))
+    let initial_index = symbols
+        .id(b"AA")
+        .ok_or(Error::InvalidInput("no valve AA"))?;
+    Ok((initial_index as usize, valves))
 }
 
 tests! {
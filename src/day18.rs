@@ -1,5 +1,8 @@
+use framework::grid::ORTHOGONAL_3D;
 framework::day!(18, parse => pt1, pt2);
+
 type Vec3 = framework::vecs::Vec3<i32>;
+type Position = framework::vecs::Vec3<usize>;
 
 fn get_bounds(cubes: &[Vec3]) -> (Vec3, Vec3) {
     cubes.iter().cloned().fold(
@@ -8,69 +11,44 @@ fn get_bounds(cubes: &[Vec3]) -> (Vec3, Vec3) {
     )
 }
 
-fn neighbors(cube: Vec3) -> impl Iterator<Item = Vec3> + Clone + std::iter::FusedIterator {
-    [-1, 1].into_iter().flat_map(move |offset| {
-        [
-            Vec3::new(cube.x + offset, cube.y, cube.z),
-            Vec3::new(cube.x, cube.y + offset, cube.z),
-            Vec3::new(cube.x, cube.y, cube.z + offset),
-        ]
-    })
-}
-
-fn pt1(cubes: &[Vec3]) -> usize {
+/// Places the cubes in a grid with a layer of air around them, returns the grid
+/// and the positions of the cubes in it.
+fn to_grid(cubes: &[Vec3]) -> (VecGrid3<bool>, Vec<Position>) {
     let (min, max) = get_bounds(cubes);
-    let (min, max) = (min - 1, max + 1);
-    let size = max + 1 - min;
-    let mut grid = vec![false; (size.x * size.y * size.z) as usize];
-    let index = |p: Vec3| -> usize {
-        let p = p - min;
-        ((p.x * size.y + p.y) * size.z + p.z) as usize
-    };
-
-    for &cube in cubes {
-        grid[index(cube)] = true;
+    let positions = (cubes.iter())
+        .map(|&cube| (cube - min + 1).to_usize())
+        .collect::<Vec<_>>();
+    let mut grid = VecGrid3::new((max - min + 3).to_usize(), |_| false);
+    for &position in &positions {
+        grid[position] = true;
     }
-    (cubes.iter().cloned())
-        .flat_map(neighbors)
-        .filter(|&p| !grid[index(p)])
-        .count()
+    (grid, positions)
 }
 
-fn pt2(cubes: &[Vec3]) -> usize {
-    let (min, max) = get_bounds(cubes);
-    let (min, max) = (min - 1, max + 1);
-    let size = max + 1 - min;
-    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
-    enum State {
-        Exterior,
-        Cube,
-        Pocket,
-    }
-    let mut grid = vec![State::Pocket; (size.x * size.y * size.z) as usize];
-    let index = |p: Vec3| -> usize {
-        let p = p - min;
-        ((p.x * size.y + p.y) * size.z + p.z) as usize
-    };
-
-    for &cube in cubes {
-        grid[index(cube)] = State::Cube;
-    }
+fn count_faces(
+    grid: &VecGrid3<bool>,
+    positions: &[Position],
+    mut is_exposed: impl FnMut(Position) -> bool,
+) -> usize {
+    (positions.iter())
+        .flat_map(|&p| {
+            ORTHOGONAL_3D
+                .into_iter()
+                .filter_map(move |o| grid.neighbor(p, o))
+        })
+        .filter(|&p| is_exposed(p))
+        .count()
+}
 
-    graph::dfs(Vec3::zero(), |point, next| -> Option<!> {
-        let state = match grid.get_mut(index(point)) {
-            Some(state @ &mut State::Pocket) => state,
-            _ => return None,
-        };
-        *state = State::Exterior;
-        next.extend(neighbors(point));
-        None
-    });
+fn pt1(cubes: &[Vec3]) -> usize {
+    let (grid, positions) = to_grid(cubes);
+    count_faces(&grid, &positions, |p| !grid[p])
+}
 
-    (cubes.iter().cloned())
-        .flat_map(neighbors)
-        .filter(|&p| matches!(grid.get(index(p)), Some(&State::Exterior)))
-        .count()
+fn pt2(cubes: &[Vec3]) -> usize {
+    let (grid, positions) = to_grid(cubes);
+    let exterior = grid.flood_fill((0, 0, 0), &ORTHOGONAL_3D, |&cube| !cube);
+    count_faces(&grid, &positions, |p| exterior[p])
 }
 
 fn parse(input: &[u8]) -> Result<Vec<Vec3>> {
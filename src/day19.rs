@@ -183,34 +183,36 @@ fn pt2(blueprints: &[Blueprint]) -> MulOutput<Vec<Int>> {
 
 fn parse(input: &[u8]) -> Result<Vec<Blueprint>> {
     use parsers::*;
-    let nr = number::<Int>();
-    #[rustfmt::skip]
-    let costs = (token(b": Each ore robot costs ").then(nr))
-        .and(token(b" ore. Each clay robot costs ").then(nr))
-        .and(token(b" ore. Each obsidian robot costs ").then(nr))
-        .and(token(b" ore and ").then(nr))
-        .and(token(b" clay. Each geode robot costs ").then(nr))
-        .and(token(b" ore and ").then(nr))
-        .trailed(token(b" obsidian."))
-        .map(|(((((
-                ore_robot_ore,
-                clay_robot_ore),
-                obsidian_robot_ore),
-                obsidian_robot_clay),
-                geode_robot_ore),
-                geode_robot_obsidian)|
-            Blueprint {
+    // Placeholders must name the type, so `{u32}` rather than the `Int` alias.
+    let blueprint = parse_format!(
+        "Blueprint {usize}: \
+        Each ore robot costs {u32} ore. \
+        Each clay robot costs {u32} ore. \
+        Each obsidian robot costs {u32} ore and {u32} clay. \
+        Each geode robot costs {u32} ore and {u32} obsidian."
+    )
+    .map(
+        |(
+            index,
+            ore_robot_ore,
+            clay_robot_ore,
+            obsidian_robot_ore,
+            obsidian_robot_clay,
+            geode_robot_ore,
+            geode_robot_obsidian,
+        )| {
+            let costs = Blueprint {
                 ore_robot_ore,
                 clay_robot_ore,
                 obsidian_robot_ore,
                 obsidian_robot_clay,
                 geode_robot_ore,
                 geode_robot_obsidian,
-            },
-        );
-    let header = token(b"Blueprint ").then(number::<usize>());
-    header
-        .and(costs)
+            };
+            (index, costs)
+        },
+    );
+    blueprint
         .sep_by(token(b'\n'))
         .map_res(|blueprints: Vec<_>| {
             for (index, &(found_index, _)) in blueprints.iter().enumerate() {
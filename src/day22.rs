@@ -28,24 +28,17 @@ fn get_boundaries<'a>(
     grid: &Grid,
     storage: &'a mut Vec<Range<usize>>,
 ) -> (&'a [Range<usize>], &'a [Range<usize>]) {
-    macro_rules! count {
-        ($iter:expr, $iter_value:ident, $x:ident, $y:ident) => {
-            $iter
-                .take_while(|&$iter_value| grid[($x, $y)] == Cell::Void)
-                .count()
-        };
+    fn boundary<'g>(
+        cells: impl DoubleEndedIterator<Item = &'g Cell> + ExactSizeIterator + Clone,
+    ) -> Range<usize> {
+        let len = cells.len();
+        let start = cells.clone().take_while(|&&c| c == Cell::Void).count();
+        let end = len - cells.rev().take_while(|&&c| c == Cell::Void).count();
+        start..end
     }
     let mut boundaries = Vec::with_capacity(grid.width() + grid.height());
-    for x in 0..grid.width() {
-        let top = count!(0..grid.height(), y, x, y);
-        let bottom = grid.height() - count!((0..grid.height()).rev(), y, x, y);
-        boundaries.push(top..bottom);
-    }
-    for y in 0..grid.height() {
-        let left = count!(0..grid.width(), x, x, y);
-        let right = grid.width() - count!((0..grid.width()).rev(), x, x, y);
-        boundaries.push(left..right);
-    }
+    boundaries.extend(grid.columns().map(boundary));
+    boundaries.extend(grid.rows().map(|row| boundary(row.iter())));
     std::mem::swap(storage, &mut boundaries);
     (&storage[..grid.width()], &storage[grid.width()..])
 }
@@ -356,32 +349,15 @@ fn pt2(input: &(Grid, Vec<Instruction>)) -> Output {
 
 fn parse(input: &[u8]) -> Result<(Grid, Vec<Instruction>)> {
     use parsers::*;
-    let input = input.trim_ascii_end();
-    let (max_len, total_len, count) = input.lines().take_while(|line| !line.is_empty()).fold(
-        (0, 0, 0),
-        |(max_len, total_len, count), line| {
-            (max_len.max(line.len()), total_len + line.len(), count + 1)
-        },
-    );
-    let additional_spacing = count * max_len - total_len;
-    let mut space_padded_input = AString::with_capacity(input.len() + additional_spacing);
-    for line in input.lines() {
-        if !line.is_empty() {
-            space_padded_input.extend_from_slice(line);
-            let new_size = space_padded_input.len() + max_len.saturating_sub(line.len());
-            space_padded_input.resize(new_size, b' ');
-        }
-        space_padded_input.push(b'\n');
-    }
-    let space_padded_input = space_padded_input.trim_ascii_end();
-
     let cell = any().map_res(|c| match c {
         b' ' => Ok(Cell::Void),
         b'.' => Ok(Cell::Open),
         b'#' => Ok(Cell::Wall),
         _ => Err(ParseError::TokenDoesNotMatch),
     });
-    let grid = grid(cell, token(b'\n'));
+    let grid = grid(cell, token(b'\n'))
+        .ragged(Cell::Void)
+        .map(|(grid, _)| grid);
 
     let turn = any().map_res(|c| match c {
         b'R' => Ok(Instruction::TurnRight),
@@ -391,8 +367,7 @@ fn parse(input: &[u8]) -> Result<(Grid, Vec<Instruction>)> {
     let instruction = number::<usize>().map(Instruction::Move).or(turn);
     let instructions = instruction.repeat_into();
 
-    grid.and(token(b'\n').then(instructions))
-        .execute(space_padded_input)
+    grid.and(token(b'\n').then(instructions)).execute(input)
 }
 
 tests! {
@@ -1,18 +1,6 @@
 framework::day!(23, parse => pt1, pt2);
 
-type Grid = VecGrid<bool>;
-
-// This is the padding applied to the input, in order have space to grow.
-// It is significantly larger than was necessary for my input:
-const OFFSET: Vec2<usize> = Vec2::new(25, 25);
-const ADDITIONAL_SIZE: Vec2<usize> = Vec2::new(88, 88);
-
-// The exact values for my input are:
-// const OFFSET: Vec2<usize> = Vec2::new(15, 14);
-// const ADDITIONAL_SIZE: Vec2<usize> = Vec2::new(68, 67);
-
-// That said, the actual allocation of the grid is a tiny fraction of the
-// overall performance.
+type Grid = SparseGrid<bool>;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 enum Cell {
@@ -21,20 +9,12 @@ enum Cell {
     Reservation(u32, u32, Offset),
 }
 
-fn pts<const LIMITED: bool>(grid: &Grid) -> (Vec<Vec2<usize>>, u32) {
-    let mut elves = Vec::new();
-    let mut grid = VecGrid::new(grid.size() + ADDITIONAL_SIZE, |pos| {
-        if pos.x < OFFSET.x || pos.y < OFFSET.y {
-            return Cell::Empty(u32::MAX);
-        }
-        match grid.get(pos - OFFSET) {
-            Some(true) => {
-                elves.push(pos);
-                Cell::Elf
-            }
-            _ => Cell::Empty(u32::MAX),
-        }
-    });
+fn pts<const LIMITED: bool>(grid: &Grid) -> (Vec<Vec2<i32>>, u32) {
+    let mut elves: Vec<_> = grid.iter().filter(|&(_, &elf)| elf).map(|(p, _)| p).collect();
+    let mut grid = SparseGrid::new(Cell::Empty(u32::MAX));
+    for &elf in &elves {
+        grid[elf] = Cell::Elf;
+    }
 
     const CHECK_DIRECTIONS: [[Offset; 3]; 4] = [
         [Offset::X_NEG_Y_NEG, Offset::Y_NEG, Offset::X_POS_Y_NEG],
@@ -99,8 +79,8 @@ fn pt1(grid: &Grid) -> usize {
 
     let (min, max) = elves.iter().fold(
         (
-            Vec2::new(usize::MAX, usize::MAX),
-            Vec2::new(usize::MIN, usize::MIN),
+            Vec2::new(i32::MAX, i32::MAX),
+            Vec2::new(i32::MIN, i32::MIN),
         ),
         |(min, max), &p| (min.min_comp(p), max.max_comp(p)),
     );
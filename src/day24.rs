@@ -14,47 +14,18 @@ enum Direction {
 
 fn pts<const BACK_AND_FORTH: bool>(grid: &Grid) -> Result<[usize; 3]> {
     let size = grid.size();
-    let mut blizzards = [
-        vec![Vec::new(); size.y],
-        vec![Vec::new(); size.y],
-        vec![Vec::new(); size.x],
-        vec![Vec::new(); size.x],
-    ];
-    for (position, direction) in grid
-        .iter()
-        .filter_map(|(position, direction)| direction.map(move |direction| (position, direction)))
-    {
-        match direction {
-            Direction::Left => blizzards[0][position.y].push(position.x),
-            Direction::Right => blizzards[1][position.y].push(position.x),
-            Direction::Up => blizzards[2][position.x].push(position.y),
-            Direction::Down => blizzards[3][position.x].push(position.y),
-        }
-    }
-
-    for direction in &mut blizzards {
-        for row_or_column in direction {
-            row_or_column.sort_unstable();
-        }
-    }
-
-    let search = #[inline]
-    |collection: usize, distance: usize, target: usize| {
-        blizzards[collection][distance]
-            .binary_search(&target)
-            .is_ok()
-    };
-
+    let valley = grid.wrapping();
     let is_free = |time: usize, position: Vec2| {
-        let offset_neg = (position + time) % size;
-        if search(0, position.y, offset_neg.x) || search(2, position.x, offset_neg.y) {
-            return false;
-        }
-        let offset_pos = (position + size - Vec2::new(time, time) % size) % size;
-        if search(1, position.y, offset_pos.x) || search(3, position.x, offset_pos.y) {
-            return false;
-        }
-        true
+        [
+            (Direction::Left, Offset::X_NEG),
+            (Direction::Right, Offset::X_POS),
+            (Direction::Up, Offset::Y_NEG),
+            (Direction::Down, Offset::Y_POS),
+        ]
+        .into_iter()
+        .all(|(direction, velocity)| {
+            valley.cell_at(position, velocity, time) != Some(&Some(direction))
+        })
     };
 
     let bottom_right = size - 1;
@@ -4,7 +4,6 @@
 #![feature(generic_const_exprs)]
 #![feature(get_many_mut)]
 #![feature(let_chains)]
-#![feature(never_type)]
 #![feature(stmt_expr_attributes)]
 
 #![allow(incomplete_features)]